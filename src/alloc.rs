@@ -0,0 +1,183 @@
+//! Opt-in allocation accounting.
+//!
+//! Building with `--features count-allocs` installs [`CountingAllocator`] as
+//! the global allocator of the binary, and the timing output then also shows
+//! the allocations of every stage. Counts are kept per thread, so days running
+//! in parallel do not mix up their numbers.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set by the first allocation through [`CountingAllocator`]
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the counting allocator is the global allocator. Enabling the
+/// feature is not enough, the binary has to install it.
+pub fn enabled() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+#[derive(Clone, Copy)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    /// Signed, memory may be freed by another thread than allocated it
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counts)) {
+    // Fails while the thread is being torn down, those allocations are not counted
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        f(&mut c);
+        counts.set(c);
+    });
+}
+
+fn allocated(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    update(|c| {
+        c.allocations += 1;
+        c.bytes += size as u64;
+        c.live += size as i64;
+        c.peak = c.peak.max(c.live);
+    });
+}
+
+fn freed(size: usize) {
+    update(|c| c.live -= size as i64);
+}
+
+/// The system allocator, counting what every thread allocates.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            freed(layout.size());
+            allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct AllocStats {
+    /// Allocations, including every reallocation
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most bytes live at once, on top of what was live before
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Counts what `f` allocates on the current thread. Always zero unless the
+/// counting allocator is installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let start = COUNTS.with(Cell::get);
+    update(|c| c.peak = c.live);
+
+    let result = f();
+
+    let end = COUNTS.with(Cell::get);
+    // An enclosing measurement still needs the highest peak
+    update(|c| c.peak = c.peak.max(start.peak));
+
+    let stats = AllocStats {
+        allocations: end.allocations - start.allocations,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live).max(0) as u64,
+    };
+    (result, stats)
+}
+
+/// Formats a byte count with three significant digits, like `format_duration`.
+pub fn format_bytes(bytes: u64) -> String {
+    let bytes_f = bytes as f64;
+    let (value, unit) = if bytes_f >= 1e9 {
+        (bytes_f / 1e9, "GB")
+    } else if bytes_f >= 1e6 {
+        (bytes_f / 1e6, "MB")
+    } else if bytes_f >= 1e3 {
+        (bytes_f / 1e3, "kB")
+    } else {
+        return format!("{bytes}B");
+    };
+
+    let decimals = if value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    format!("{value:.decimals$}{unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Measuring needs the counting allocator, so that is tested in
+    // tests/alloc.rs where it does not replace the allocator of every test
+
+    #[test]
+    fn test_not_enabled() {
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.50kB", format_bytes(1500));
+        assert_eq!("24.0MB", format_bytes(24_000_000));
+        assert_eq!("123GB", format_bytes(123_456_789_012));
+    }
+}
@@ -0,0 +1,255 @@
+//! Recorded answers, stored as one file per day in the answers directory.
+//!
+//! Every line records one fact about the answer to a part, comments start
+//! with `#`:
+//!
+//! ```text
+//! 1 = 1097          part 1 is 1097
+//! 2 != 1478326656   1478326656 was submitted and is wrong
+//! 2 < 2859243744    2859243744 was too high
+//! 2 > 1410470448    1410470448 was too low
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::solution::Part;
+
+pub const ANSWERS_DIR: &str = "answers";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Record {
+    Correct(String),
+    Wrong(String),
+    TooHigh(i128),
+    TooLow(i128),
+}
+
+impl Record {
+    pub fn parse(kind: &str, value: &str) -> Result<Record, String> {
+        let bound = || {
+            value
+                .parse::<i128>()
+                .map_err(|_| format!("Bound `{value}` is not a number"))
+        };
+
+        match kind {
+            "=" | "correct" => Ok(Record::Correct(value.to_string())),
+            "!=" | "wrong" => Ok(Record::Wrong(value.to_string())),
+            "<" | "high" => Ok(Record::TooHigh(bound()?)),
+            ">" | "low" => Ok(Record::TooLow(bound()?)),
+            _ => Err(format!(
+                "Unknown answer kind `{kind}`, expected correct, wrong, high or low"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Check {
+    Correct,
+    Incorrect { expected: String },
+    KnownWrong,
+    TooHigh { bound: i128 },
+    TooLow { bound: i128 },
+    Unknown,
+}
+
+impl Check {
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Check::Correct | Check::Unknown)
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => write!(f, "correct"),
+            Check::Incorrect { expected } => write!(f, "incorrect, expected {expected}"),
+            Check::KnownWrong => write!(f, "known wrong answer"),
+            Check::TooHigh { bound } => write!(f, "too high, answer is below {bound}"),
+            Check::TooLow { bound } => write!(f, "too low, answer is above {bound}"),
+            Check::Unknown => write!(f, "no recorded answer"),
+        }
+    }
+}
+
+pub struct AnswerFile {
+    path: PathBuf,
+    entries: Vec<(Part, Record)>,
+}
+
+impl AnswerFile {
+    pub fn path(dir: &Path, day: u32) -> PathBuf {
+        dir.join(format!("day{day}.txt"))
+    }
+
+    /// Loads the answers for a day. A missing file means nothing is recorded yet.
+    pub fn load(dir: &Path, day: u32) -> Result<Self, String> {
+        let path = Self::path(dir, day);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(_) if !path.exists() => String::new(),
+            Err(e) => return Err(format!("Could not read {}: {e}", path.display())),
+        };
+
+        Self::parse(path, &content)
+    }
+
+    pub fn parse(path: PathBuf, content: &str) -> Result<Self, String> {
+        let mut entries = Vec::new();
+
+        for (line_idx, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |e: String| format!("{}:{}: {e}", path.display(), line_idx + 1);
+            let mut parts = line.split_whitespace();
+            let (Some(part), Some(kind), Some(value), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(error(format!(
+                    "Expected `<part> <kind> <value>`, got `{line}`"
+                )));
+            };
+
+            let part = part.parse::<Part>().map_err(error)?;
+            let record = Record::parse(kind, value).map_err(error)?;
+            entries.push((part, record));
+        }
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn check(&self, part: Part, answer: &str) -> Check {
+        let records = || {
+            self.entries
+                .iter()
+                .filter(move |(p, _)| *p == part)
+                .map(|(_, r)| r)
+        };
+
+        if let Some(expected) = records().find_map(|r| match r {
+            Record::Correct(v) => Some(v),
+            _ => None,
+        }) {
+            return if expected == answer {
+                Check::Correct
+            } else {
+                Check::Incorrect {
+                    expected: expected.clone(),
+                }
+            };
+        }
+
+        if records().any(|r| *r == Record::Wrong(answer.to_string())) {
+            return Check::KnownWrong;
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Check::Unknown;
+        };
+
+        for record in records() {
+            match *record {
+                Record::TooHigh(bound) if value >= bound => return Check::TooHigh { bound },
+                Record::TooLow(bound) if value <= bound => return Check::TooLow { bound },
+                _ => {}
+            }
+        }
+
+        Check::Unknown
+    }
+
+    /// Adds a record. A new correct answer replaces any previously recorded one.
+    pub fn record(&mut self, part: Part, record: Record) {
+        if let Record::Correct(_) = record {
+            self.entries
+                .retain(|(p, r)| *p != part || !matches!(r, Record::Correct(_)));
+        }
+
+        if !self.entries.contains(&(part, record.clone())) {
+            self.entries.push((part, record));
+        }
+        self.entries.sort_by_key(|(p, _)| *p);
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let mut content = String::new();
+        for (part, record) in &self.entries {
+            let line = match record {
+                Record::Correct(v) => format!("{part} = {v}\n"),
+                Record::Wrong(v) => format!("{part} != {v}\n"),
+                Record::TooHigh(v) => format!("{part} < {v}\n"),
+                Record::TooLow(v) => format!("{part} > {v}\n"),
+            };
+            content.push_str(&line);
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        }
+        fs::write(&self.path, content)
+            .map_err(|e| format!("Could not write {}: {e}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(content: &str) -> AnswerFile {
+        AnswerFile::parse(PathBuf::from("day9.txt"), content).unwrap()
+    }
+
+    #[test]
+    fn test_correct() {
+        let answers = answers("1 = 50\n# comment\n2 = 24 # trailing");
+        assert_eq!(Check::Correct, answers.check(Part::One, "50"));
+        assert_eq!(
+            Check::Incorrect {
+                expected: "24".to_string()
+            },
+            answers.check(Part::Two, "40")
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let answers = answers("2 > 222529760\n2 != 1478326656\n2 < 2859243744");
+        assert_eq!(Check::KnownWrong, answers.check(Part::Two, "1478326656"));
+        assert_eq!(
+            Check::TooLow { bound: 222529760 },
+            answers.check(Part::Two, "1000")
+        );
+        assert_eq!(
+            Check::TooHigh { bound: 2859243744 },
+            answers.check(Part::Two, "2859243744")
+        );
+        assert_eq!(Check::Unknown, answers.check(Part::Two, "1410501884"));
+        assert_eq!(Check::Unknown, answers.check(Part::One, "1000"));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut answers = answers("2 < 100");
+        answers.record(Part::Two, Record::Correct("40".to_string()));
+        answers.record(Part::One, Record::Correct("7".to_string()));
+        answers.record(Part::One, Record::Correct("8".to_string()));
+
+        assert_eq!(Check::Correct, answers.check(Part::One, "8"));
+        assert_eq!(Check::Correct, answers.check(Part::Two, "40"));
+        assert_eq!(3, answers.entries.len());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(AnswerFile::parse(PathBuf::from("day1.txt"), "3 = 1").is_err());
+        assert!(AnswerFile::parse(PathBuf::from("day1.txt"), "1 < abc").is_err());
+        assert!(AnswerFile::parse(PathBuf::from("day1.txt"), "1 =").is_err());
+    }
+}
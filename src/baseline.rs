@@ -0,0 +1,304 @@
+//! Stored timings to detect performance regressions.
+//!
+//! A baseline file has one line per measured stage, `<day> <stage> <nanos>`,
+//! where the stage is `parse`, `1` or `2` and the time is the benchmark median.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::solution::{Part, Stage};
+use crate::timed::{format_duration, parse_duration};
+
+pub const BASELINE_FILE: &str = "baseline.txt";
+
+/// Time budget of a part, unless overridden with --limit
+pub const DEFAULT_LIMIT: Duration = Duration::from_secs(1);
+
+/// Larger budgets of the days that do not fit in [`DEFAULT_LIMIT`]
+pub const SLOW_DAY_LIMITS: [(u32, Duration); 4] = [
+    (2, Duration::from_secs(2)),
+    (4, Duration::from_secs(2)),
+    (8, Duration::from_secs(2)),
+    (10, Duration::from_secs(5)),
+];
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Baseline {
+    pub times: BTreeMap<(u32, Stage), Duration>,
+}
+
+fn stage_name(stage: Stage) -> String {
+    match stage {
+        Stage::Parse => "parse".to_string(),
+        Stage::Solve(part) => part.to_string(),
+    }
+}
+
+impl Baseline {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut times = BTreeMap::new();
+
+        for (line_idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = || format!("Invalid baseline entry on line {}: `{line}`", line_idx + 1);
+            let mut fields = line.split_whitespace();
+            let (Some(day), Some(stage), Some(nanos), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(error());
+            };
+
+            let day = day.parse().map_err(|_| error())?;
+            let stage = match stage {
+                "parse" => Stage::Parse,
+                part => Stage::Solve(part.parse::<Part>().map_err(|_| error())?),
+            };
+            let nanos = nanos.parse().map_err(|_| error())?;
+            times.insert((day, stage), Duration::from_nanos(nanos));
+        }
+
+        Ok(Self { times })
+    }
+
+    /// Loads a baseline, a missing file is an empty baseline.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        Self::parse(&content).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string())
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+
+    /// Adds or replaces the times in `other`.
+    pub fn merge(&mut self, other: Baseline) {
+        self.times.extend(other.times);
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (&(day, stage), time) in &self.times {
+            writeln!(f, "{day} {} {}", stage_name(stage), time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// Maximum time allowed to get the answer to a part, including parsing.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TimeLimits {
+    pub default: Option<Duration>,
+    pub days: BTreeMap<u32, Duration>,
+}
+
+impl TimeLimits {
+    /// Adds a limit given as `<time>` for every day or `<day>=<time>`.
+    pub fn add(&mut self, limit: &str) -> Result<(), String> {
+        match limit.split_once('=') {
+            Some((day, time)) => {
+                let day = day
+                    .parse()
+                    .map_err(|_| format!("Invalid day in limit `{limit}`"))?;
+                self.days.insert(day, parse_duration(time)?);
+            }
+            None => self.default = Some(parse_duration(limit)?),
+        }
+        Ok(())
+    }
+
+    /// Falls back to [`DEFAULT_LIMIT`] and [`SLOW_DAY_LIMITS`] when no limit
+    /// was given for every day.
+    pub fn or_defaults(mut self) -> Self {
+        if self.default.is_none() {
+            self.default = Some(DEFAULT_LIMIT);
+            for (day, limit) in SLOW_DAY_LIMITS {
+                self.days.entry(day).or_insert(limit);
+            }
+        }
+        self
+    }
+
+    pub fn for_day(&self, day: u32) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.default)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StageComparison {
+    pub day: u32,
+    pub stage: Stage,
+    pub baseline: Option<Duration>,
+    pub current: Duration,
+    /// Relative change against the baseline, 0.1 is 10% slower
+    pub change: Option<f64>,
+    pub regressed: bool,
+    /// The limit that the part exceeded, if any
+    pub over_limit: Option<Duration>,
+}
+
+impl StageComparison {
+    pub fn failed(&self) -> bool {
+        self.regressed || self.over_limit.is_some()
+    }
+}
+
+/// Compares `current` with `baseline`. A stage has regressed when it got more
+/// than `threshold` slower, e.g. 0.1 for 10%.
+pub fn compare(
+    baseline: &Baseline,
+    current: &Baseline,
+    threshold: f64,
+    limits: &TimeLimits,
+) -> Vec<StageComparison> {
+    current
+        .times
+        .iter()
+        .map(|(&(day, stage), &time)| {
+            let base = baseline.times.get(&(day, stage)).copied();
+            let change = base
+                .filter(|b| !b.is_zero())
+                .map(|b| time.as_secs_f64() / b.as_secs_f64() - 1.0);
+
+            let over_limit = match stage {
+                Stage::Parse => None,
+                Stage::Solve(_) => {
+                    let parse = current
+                        .times
+                        .get(&(day, Stage::Parse))
+                        .copied()
+                        .unwrap_or_default();
+                    limits.for_day(day).filter(|&limit| parse + time > limit)
+                }
+            };
+
+            StageComparison {
+                day,
+                stage,
+                baseline: base,
+                current: time,
+                change,
+                regressed: change.is_some_and(|c| c > threshold),
+                over_limit,
+            }
+        })
+        .collect()
+}
+
+impl Display for StageComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let baseline = self.baseline.map_or("-".to_string(), format_duration);
+        let change = self
+            .change
+            .map_or("new".to_string(), |c| format!("{:+.1}%", c * 100.0));
+
+        write!(
+            f,
+            "{:>3}  {:<7} {:>9} {:>9} {:>8}",
+            self.day,
+            self.stage.to_string(),
+            baseline,
+            format_duration(self.current),
+            change
+        )?;
+        if self.regressed {
+            write!(f, "  SLOWER")?;
+        }
+        if let Some(limit) = self.over_limit {
+            write!(f, "  OVER LIMIT ({})", format_duration(limit))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let content = "1 parse 528\n1 1 29\n8 2 1500000000\n";
+        let baseline = Baseline::parse(content).unwrap();
+        assert_eq!(
+            Some(&Duration::from_nanos(29)),
+            baseline.times.get(&(1, Stage::Solve(Part::One)))
+        );
+        assert_eq!(content, baseline.to_string());
+
+        assert!(Baseline::parse("1 3 100").is_err());
+        assert!(Baseline::parse("1 parse").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = Baseline::parse("4 parse 1000000\n4 1 10000000\n4 2 10000000").unwrap();
+        let mut current = Baseline::default();
+        current.times.insert((4, Stage::Parse), ms(1));
+        current.times.insert((4, Stage::Solve(Part::One)), ms(11));
+        current.times.insert((4, Stage::Solve(Part::Two)), ms(12));
+        current.times.insert((8, Stage::Solve(Part::One)), ms(30));
+
+        let mut limits = TimeLimits::default();
+        limits.add("1s").unwrap();
+        limits.add("8=20ms").unwrap();
+
+        let result = compare(&baseline, &current, 0.15, &limits);
+        let failed: Vec<_> = result
+            .iter()
+            .map(|c| (c.day, c.stage, c.regressed, c.over_limit))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (4, Stage::Parse, false, None),
+                (4, Stage::Solve(Part::One), false, None),
+                (4, Stage::Solve(Part::Two), true, None),
+                (8, Stage::Solve(Part::One), false, Some(ms(20))),
+            ],
+            failed
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut limits = TimeLimits::default();
+        assert_eq!(None, limits.for_day(1));
+        limits.add("1s").unwrap();
+        limits.add("10=5s").unwrap();
+        assert_eq!(Some(ms(1000)), limits.for_day(1));
+        assert_eq!(Some(ms(5000)), limits.for_day(10));
+        assert!(limits.add("x=1s").is_err());
+    }
+
+    #[test]
+    fn test_default_limits() {
+        let mut limits = TimeLimits::default();
+        limits.add("10=8s").unwrap();
+        let limits = limits.or_defaults();
+        assert_eq!(Some(DEFAULT_LIMIT), limits.for_day(1));
+        assert_eq!(Some(ms(2000)), limits.for_day(2));
+        assert_eq!(Some(ms(8000)), limits.for_day(10));
+
+        let mut limits = TimeLimits::default();
+        limits.add("100ms").unwrap();
+        let limits = limits.or_defaults();
+        assert_eq!(Some(ms(100)), limits.for_day(1));
+        assert_eq!(Some(ms(100)), limits.for_day(2));
+    }
+}
@@ -0,0 +1,944 @@
+use std::path::PathBuf;
+
+use aoc25::answers::Record;
+use aoc25::baseline::{BASELINE_FILE, TimeLimits};
+use aoc25::examples::PUZZLE_DIR;
+use aoc25::inputs::InputLocator;
+use aoc25::registry::registry;
+use aoc25::report::ReportFormat;
+use aoc25::solution::Part;
+use aoc25::timed::{BenchConfig, parse_duration};
+
+use crate::scaffold::MAX_DAY;
+
+pub const USAGE: &str = "Usage: aoc25 [run] <days...|all> [options]
+       aoc25 bench <days...|all> [options] [bench options]
+       aoc25 baseline <save|compare> <days...|all> [options] [bench options] [baseline options]
+       aoc25 verify [days...|all] [--input-dir <dir>]
+       aoc25 compare <days...|all> [options]
+       aoc25 answer <day> <part> <correct|wrong|high|low> <value>
+       aoc25 examples <day> [--check] [--dir <dir>]
+       aoc25 new <day> [--dir <dir>]
+       aoc25 fuzz [days...|all] [--iterations <n>] [--seed <n>]
+       aoc25 generate <day> [--size <n>] [--seed <n>] [--output <path>]
+       aoc25 watch <day> [options]
+
+Commands:
+  run                  Run the selected days (the default), several days are summarized in a table
+  bench                Benchmark parsing and both parts of the selected days
+  baseline save        Benchmark the selected days and store their timings as the baseline
+  baseline compare     Benchmark the selected days and fail if any part got slower than the baseline
+  verify               Check every day against its recorded answers, all days if none are given
+  compare              Run every implementation of the selected parts, the registered solution
+                       and its alternates, and check that they agree
+  answer               Record an answer that was accepted, rejected, too high or too low
+  examples             Print a test module with the examples and answers of a saved puzzle
+                       description, or run the day on them with --check
+  watch                Run the tests of a day and then the day itself whenever its sources or
+                       input change, showing which answers changed
+  fuzz                 Feed the parsers of the selected days, all if none are given, with
+                       mutated random inputs and report any input they panic on
+  generate             Write a random input in the format of a day, to stdout or <path>
+  new                  Create and register src/dayN.rs from a template, with tests for the
+                       examples of the saved description if there is one
+
+Arguments:
+  <days...>            One or more days, e.g. `1 3 5` or `1-5`
+  all                  Every day
+
+Options:
+  -p, --part <1|2>     Only run the given part (may be repeated)
+  -i, --input <path>   Read the puzzle input from <path> instead of the input directory
+  --input-dir <dir>    Directory with the inputs, defaults to $AOC25_INPUTS or inputs
+  -n, --name <name>    Use the named input dayN.<name>.txt or dayN/<name>.txt instead of
+                       dayN.txt, e.g. `alt` or `example1`
+  --profile            Print a tree of the timing spans recorded while running
+  --report <json|csv>  Write a machine readable report of the run to stdout
+  --report-file <path> Write the report to <path>, the format defaults to its extension
+  -j, --jobs <n>       Run up to <n> days at the same time (default 1)
+  --limit [<day>=]<time>
+                       Time budget for parsing plus a part, for every day or only the
+                       given one (default 1s, 2s for days 2, 4 and 8, 5s for day 10,
+                       may be repeated)
+  -h, --help           Print this help
+
+Bench options:
+  --warmup <time>      Time to run each stage before sampling, e.g. `200ms` (default 200ms)
+  --budget <time>      Time to spend sampling each stage, e.g. `2s` (default 1s)
+  --min-samples <n>    Take at least <n> samples, even if over budget (default 10)
+
+Baseline options:
+  --file <path>        Baseline to save to or compare with (default baseline.txt)
+  --threshold <n>      Percentage a stage may get slower before it fails (default 10)
+  --limit [<day>=]<time>
+                       Fail if parsing plus a part takes longer than <time>, for every
+                       day or only the given one, e.g. `1s` or `8=5s` (defaults as for
+                       run, may be repeated)
+
+Fuzz options:
+  --iterations <n>     Number of inputs per day (default 10000)
+  --seed <n>           Seed of the random generator (default 1)
+
+Generate options:
+  --size <n>           Number of lines or items, e.g. rotations on day 1 or machines on
+                       day 10 (default the size of the real input)
+  --seed <n>           Seed of the random generator (default 1)
+  -o, --output <path>  Write the input to <path> instead of stdout
+
+Examples and new options:
+  --dir <dir>          Directory with the saved descriptions dayN.html or dayN.md
+                       (default puzzles)
+  --check              Run the day on the examples instead of printing tests";
+
+#[derive(PartialEq, Debug)]
+pub struct RunArgs {
+    pub days: Vec<u32>,
+    pub parts: Vec<Part>,
+    pub input: Option<PathBuf>,
+    pub input_dir: Option<PathBuf>,
+    pub input_name: Option<String>,
+    /// Collect and print timing spans
+    pub profile: bool,
+}
+
+impl RunArgs {
+    pub fn input_path(&self, day: u32) -> Result<PathBuf, String> {
+        match &self.input {
+            Some(input) => Ok(input.clone()),
+            None => {
+                InputLocator::from_env(self.input_dir.as_deref(), self.input_name.clone()).find(day)
+            }
+        }
+    }
+
+    /// Whether the days run on their real inputs, which recorded answers apply to
+    pub fn is_real_input(&self) -> bool {
+        self.input.is_none() && self.input_name.is_none()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct ReportArgs {
+    pub format: ReportFormat,
+    /// Write the report here instead of to stdout
+    pub path: Option<PathBuf>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum BaselineAction {
+    Save,
+    Compare {
+        /// Allowed relative slowdown, 0.1 is 10%
+        threshold: f64,
+        limits: TimeLimits,
+    },
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Run {
+        run: RunArgs,
+        report: Option<ReportArgs>,
+        /// Number of days to run at the same time
+        jobs: usize,
+        limits: TimeLimits,
+    },
+    Bench {
+        run: RunArgs,
+        config: BenchConfig,
+    },
+    Baseline {
+        run: RunArgs,
+        config: BenchConfig,
+        file: PathBuf,
+        action: BaselineAction,
+    },
+    Verify {
+        days: Vec<u32>,
+        input_dir: Option<PathBuf>,
+    },
+    Compare {
+        run: RunArgs,
+    },
+    Answer {
+        day: u32,
+        part: Part,
+        record: Record,
+    },
+    Examples {
+        day: u32,
+        dir: PathBuf,
+        /// Run the day on the examples instead of printing a test module
+        check: bool,
+    },
+    New {
+        day: u32,
+        dir: PathBuf,
+    },
+    Fuzz {
+        days: Vec<u32>,
+        iterations: u64,
+        seed: u64,
+    },
+    Generate {
+        day: u32,
+        size: Option<usize>,
+        seed: u64,
+        output: Option<PathBuf>,
+    },
+    Watch {
+        run: RunArgs,
+    },
+    Help,
+}
+
+/// Days with a registered solution, in order.
+fn registered_days() -> Vec<u32> {
+    registry().days().map(|(day, _)| day).collect()
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    let days = registered_days();
+    match day.parse::<u32>() {
+        Ok(d) if days.contains(&d) => Ok(d),
+        _ => Err(format!(
+            "Invalid day `{day}`, expected a registered day ({}-{})",
+            days.first().unwrap_or(&0),
+            days.last().unwrap_or(&0)
+        )),
+    }
+}
+
+fn parse_days(arg: &str) -> Result<Vec<u32>, String> {
+    let days = registered_days();
+    if arg == "all" {
+        return Ok(days);
+    }
+
+    match arg.split_once("-") {
+        Some((first, last)) => {
+            let first = parse_day(first)?;
+            let last = parse_day(last)?;
+            if first > last {
+                return Err(format!("Invalid day range `{arg}`"));
+            }
+            Ok((first..=last).filter(|d| days.contains(d)).collect())
+        }
+        None => Ok(vec![parse_day(arg)?]),
+    }
+}
+
+/// Parses the day selection shared by every command that runs days. Options
+/// that are not shared are passed to `option`, which returns whether it
+/// consumed the option. Returns `None` if help was requested.
+fn parse_run_args(
+    mut args: impl Iterator<Item = String>,
+    mut option: impl FnMut(&str, &mut dyn Iterator<Item = String>) -> Result<bool, String>,
+) -> Result<Option<RunArgs>, String> {
+    let mut days = Vec::new();
+    let mut parts = Vec::new();
+    let mut input = None;
+    let mut input_dir = None;
+    let mut input_name = None;
+    let mut profile = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--profile" => profile = true,
+            "-p" | "--part" => {
+                let part = args.next().ok_or("Missing value for --part")?;
+                parts.push(part.parse()?);
+            }
+            "-i" | "--input" => {
+                let path = args.next().ok_or("Missing value for --input")?;
+                input = Some(PathBuf::from(path));
+            }
+            "--input-dir" => input_dir = Some(PathBuf::from(option_value(&arg, &mut args)?)),
+            "-n" | "--name" => input_name = Some(option_value(&arg, &mut args)?),
+            a if a.starts_with("-") => {
+                if !option(a, &mut args)? {
+                    return Err(format!("Unknown option `{a}`"));
+                }
+            }
+            a => days.extend(parse_days(a)?),
+        }
+    }
+
+    if days.is_empty() {
+        return Err("No days selected".to_string());
+    }
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single day".to_string());
+    }
+    if input.is_some() && input_name.is_some() {
+        return Err("--input can not be combined with --name".to_string());
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    if parts.is_empty() {
+        parts = Part::ALL.to_vec();
+    }
+
+    Ok(Some(RunArgs {
+        days,
+        parts,
+        input,
+        input_dir,
+        input_name,
+        profile,
+    }))
+}
+
+fn option_value(name: &str, args: &mut dyn Iterator<Item = String>) -> Result<String, String> {
+    args.next().ok_or(format!("Missing value for {name}"))
+}
+
+fn parse_run(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut format = None;
+    let mut path = None;
+    let mut jobs = 1;
+    let mut limits = TimeLimits::default();
+
+    let run = parse_run_args(args, |option, args| {
+        match option {
+            "--report" => format = Some(option_value(option, args)?.parse()?),
+            "--report-file" => path = Some(PathBuf::from(option_value(option, args)?)),
+            "-j" | "--jobs" => {
+                jobs = option_value(option, args)?
+                    .parse()
+                    .ok()
+                    .filter(|&j| j > 0)
+                    .ok_or("Invalid value for --jobs")?
+            }
+            "--limit" => limits.add(&option_value(option, args)?)?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    let Some(run) = run else {
+        return Ok(Command::Help);
+    };
+
+    let format = match (format, &path) {
+        (Some(format), _) => Some(format),
+        (None, Some(path)) => Some(ReportFormat::from_path(path).ok_or(format!(
+            "Can not tell the report format of {}, use --report",
+            path.display()
+        ))?),
+        (None, None) => None,
+    };
+    let report = format.map(|format| ReportArgs { format, path });
+
+    Ok(Command::Run {
+        run,
+        report,
+        jobs,
+        limits: limits.or_defaults(),
+    })
+}
+
+/// Parses an option of the benchmark config, returns whether it was one.
+fn bench_option(
+    config: &mut BenchConfig,
+    option: &str,
+    args: &mut dyn Iterator<Item = String>,
+) -> Result<bool, String> {
+    match option {
+        "--warmup" => config.warmup = parse_duration(&option_value(option, args)?)?,
+        "--budget" => config.budget = parse_duration(&option_value(option, args)?)?,
+        "--min-samples" => {
+            config.min_samples = option_value(option, args)?
+                .parse()
+                .map_err(|_| "Invalid value for --min-samples")?
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_bench(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut config = BenchConfig::default();
+
+    let run = parse_run_args(args, |option, args| bench_option(&mut config, option, args))?;
+
+    Ok(run.map_or(Command::Help, |run| Command::Bench { run, config }))
+}
+
+fn parse_baseline(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let action = args
+        .next()
+        .ok_or("Missing baseline action, expected save or compare")?;
+    let compare = match action.as_str() {
+        "-h" | "--help" => return Ok(Command::Help),
+        "save" => false,
+        "compare" => true,
+        a => {
+            return Err(format!(
+                "Unknown baseline action `{a}`, expected save or compare"
+            ));
+        }
+    };
+
+    let mut config = BenchConfig::default();
+    let mut file = PathBuf::from(BASELINE_FILE);
+    let mut threshold = 10.0;
+    let mut limits = TimeLimits::default();
+
+    let run = parse_run_args(args, |option, args| {
+        match option {
+            "--file" => file = PathBuf::from(option_value(option, args)?),
+            "--threshold" if compare => {
+                threshold = option_value(option, args)?
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|_| "Invalid value for --threshold")?
+            }
+            "--limit" if compare => limits.add(&option_value(option, args)?)?,
+            _ => return bench_option(&mut config, option, args),
+        }
+        Ok(true)
+    })?;
+    let Some(run) = run else {
+        return Ok(Command::Help);
+    };
+
+    let action = if compare {
+        BaselineAction::Compare {
+            threshold: threshold / 100.0,
+            limits: limits.or_defaults(),
+        }
+    } else {
+        BaselineAction::Save
+    };
+
+    Ok(Command::Baseline {
+        run,
+        config,
+        file,
+        action,
+    })
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut days = Vec::new();
+    let mut input_dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--input-dir" => input_dir = Some(PathBuf::from(option_value(&arg, &mut args)?)),
+            a if a.starts_with("-") => return Err(format!("Unknown option `{a}`")),
+            a => days.extend(parse_days(a)?),
+        }
+    }
+
+    if days.is_empty() {
+        days = registered_days();
+    }
+    days.sort_unstable();
+    days.dedup();
+
+    Ok(Command::Verify { days, input_dir })
+}
+
+fn parse_answer(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut next = |name: &str| args.next().ok_or(format!("Missing <{name}>"));
+
+    let day = parse_day(&next("day")?)?;
+    let part = next("part")?.parse()?;
+    let kind = next("kind")?;
+    let record = Record::parse(&kind, &next("value")?)?;
+
+    if let Ok(extra) = next("") {
+        return Err(format!("Unexpected argument `{extra}`"));
+    }
+
+    Ok(Command::Answer { day, part, record })
+}
+
+fn parse_examples(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut dir = PathBuf::from(PUZZLE_DIR);
+    let mut check = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--dir" => dir = PathBuf::from(option_value(&arg, &mut args)?),
+            "--check" => check = true,
+            a if a.starts_with("-") => return Err(format!("Unknown option `{a}`")),
+            a if day.is_none() => day = Some(parse_day(a)?),
+            a => return Err(format!("Unexpected argument `{a}`")),
+        }
+    }
+
+    let day = day.ok_or("Missing <day>")?;
+    Ok(Command::Examples { day, dir, check })
+}
+
+fn parse_compare(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match parse_run_args(args, |_, _| Ok(false))? {
+        Some(run) => Ok(Command::Compare { run }),
+        None => Ok(Command::Help),
+    }
+}
+
+fn parse_watch(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let Some(run) = parse_run_args(args, |_, _| Ok(false))? else {
+        return Ok(Command::Help);
+    };
+    if run.days.len() > 1 {
+        return Err("Only one day can be watched".to_string());
+    }
+    Ok(Command::Watch { run })
+}
+
+fn parse_fuzz(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut days = Vec::new();
+    let mut iterations = 10000;
+    let mut seed = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--iterations" => {
+                let value = option_value(&arg, &mut args)?;
+                iterations = value
+                    .parse()
+                    .map_err(|_| format!("Invalid number of iterations `{value}`"))?;
+            }
+            "--seed" => {
+                let value = option_value(&arg, &mut args)?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("Invalid seed `{value}`"))?;
+            }
+            a if a.starts_with("-") => return Err(format!("Unknown option `{a}`")),
+            a => days.extend(parse_days(a)?),
+        }
+    }
+
+    if days.is_empty() {
+        days = registered_days();
+    }
+    days.sort_unstable();
+    days.dedup();
+
+    Ok(Command::Fuzz {
+        days,
+        iterations,
+        seed,
+    })
+}
+
+fn parse_generate(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut size = None;
+    let mut seed = 1;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--size" => {
+                let value = option_value(&arg, &mut args)?;
+                size = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid size `{value}`"))?,
+                );
+            }
+            "--seed" => {
+                let value = option_value(&arg, &mut args)?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("Invalid seed `{value}`"))?;
+            }
+            "-o" | "--output" => output = Some(PathBuf::from(option_value(&arg, &mut args)?)),
+            a if a.starts_with("-") => return Err(format!("Unknown option `{a}`")),
+            a if day.is_none() => day = Some(parse_day(a)?),
+            a => return Err(format!("Unexpected argument `{a}`")),
+        }
+    }
+
+    let day = day.ok_or("Missing <day>")?;
+    Ok(Command::Generate {
+        day,
+        size,
+        seed,
+        output,
+    })
+}
+
+fn parse_new(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut dir = PathBuf::from(PUZZLE_DIR);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--dir" => dir = PathBuf::from(option_value(&arg, &mut args)?),
+            a if a.starts_with("-") => return Err(format!("Unknown option `{a}`")),
+            a if day.is_none() => match a.parse::<u32>() {
+                Ok(d) if registered_days().contains(&d) => {
+                    return Err(format!("Day {d} is already registered"));
+                }
+                Ok(d) if (1..=MAX_DAY).contains(&d) => day = Some(d),
+                _ => return Err(format!("Invalid day `{a}`, expected 1-{MAX_DAY}")),
+            },
+            a => return Err(format!("Unexpected argument `{a}`")),
+        }
+    }
+
+    let day = day.ok_or("Missing <day>")?;
+    Ok(Command::New { day, dir })
+}
+
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let Some(first) = args.next() else {
+        return Err("No days selected".to_string());
+    };
+
+    match first.as_str() {
+        "run" => parse_run(args),
+        "bench" => parse_bench(args),
+        "baseline" => parse_baseline(args),
+        "verify" => parse_verify(args),
+        "compare" => parse_compare(args),
+        "answer" => parse_answer(args),
+        "examples" => parse_examples(args),
+        "new" => parse_new(args),
+        "fuzz" => parse_fuzz(args),
+        "generate" => parse_generate(args),
+        "watch" => parse_watch(args),
+        _ => parse_run([first].into_iter().chain(args)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc25::baseline::DEFAULT_LIMIT;
+
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command, String> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    fn parse_run(args: &str) -> RunArgs {
+        match parse(args) {
+            Ok(Command::Run { run, .. }) => run,
+            other => panic!("Expected run command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_single_day() {
+        let args = parse_run("4");
+        assert_eq!(vec![4], args.days);
+        assert_eq!(vec![Part::One, Part::Two], args.parts);
+        assert_eq!(None, args.input_dir);
+        assert!(args.is_real_input());
+    }
+
+    #[test]
+    fn test_all_days() {
+        let args = parse_run("run all -p 2");
+        assert_eq!(registered_days(), args.days);
+        assert_eq!(vec![Part::Two], args.parts);
+    }
+
+    #[test]
+    fn test_day_range() {
+        let args = parse_run("1-3 7");
+        assert_eq!(vec![1, 2, 3, 7], args.days);
+        let args = parse_run("3 7 1-3");
+        assert_eq!(vec![1, 2, 3, 7], args.days);
+    }
+
+    #[test]
+    fn test_input() {
+        let args = parse_run("9 --input example.txt");
+        assert_eq!(Ok(PathBuf::from("example.txt")), args.input_path(9));
+        assert!(!args.profile);
+        assert!(!args.is_real_input());
+    }
+
+    #[test]
+    fn test_named_input() {
+        let args = parse_run("5 --input-dir puzzles -n example1");
+        assert_eq!(Some(PathBuf::from("puzzles")), args.input_dir);
+        assert_eq!(Some("example1".to_string()), args.input_name);
+        assert!(!args.is_real_input());
+
+        assert!(parse("5 --input example.txt --name alt").is_err());
+    }
+
+    #[test]
+    fn test_profile() {
+        assert!(parse_run("8 --profile").profile);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = |args| match parse(args) {
+            Ok(Command::Run { report, .. }) => report,
+            other => panic!("Expected run command, got {other:?}"),
+        };
+
+        assert_eq!(None, report("1"));
+        assert_eq!(
+            Some(ReportArgs {
+                format: ReportFormat::Json,
+                path: None
+            }),
+            report("1 --report json")
+        );
+        assert_eq!(
+            Some(ReportArgs {
+                format: ReportFormat::Csv,
+                path: Some(PathBuf::from("runs.csv"))
+            }),
+            report("1 --report-file runs.csv")
+        );
+        assert!(parse("1 --report-file runs.txt").is_err());
+        assert!(parse("1 --report xml").is_err());
+    }
+
+    #[test]
+    fn test_jobs_and_limits() {
+        match parse("all -j 4 --limit 8=5s") {
+            Ok(Command::Run { jobs, limits, .. }) => {
+                assert_eq!(4, jobs);
+                assert_eq!(Some(DEFAULT_LIMIT), limits.for_day(1));
+                assert_eq!(Some(Duration::from_secs(5)), limits.for_day(8));
+                assert_eq!(Some(Duration::from_secs(5)), limits.for_day(10));
+            }
+            other => panic!("Expected run command, got {other:?}"),
+        }
+        match parse("1 --limit 100ms") {
+            Ok(Command::Run { jobs, limits, .. }) => {
+                assert_eq!(1, jobs);
+                assert_eq!(Some(Duration::from_millis(100)), limits.for_day(1));
+            }
+            other => panic!("Expected run command, got {other:?}"),
+        }
+        assert!(parse("all -j 0").is_err());
+    }
+
+    #[test]
+    fn test_bench() {
+        match parse("bench 4 -p 2 --budget 2s --warmup 50ms --min-samples 3") {
+            Ok(Command::Bench { run, config }) => {
+                assert_eq!(vec![4], run.days);
+                assert_eq!(vec![Part::Two], run.parts);
+                assert_eq!(Duration::from_secs(2), config.budget);
+                assert_eq!(Duration::from_millis(50), config.warmup);
+                assert_eq!(3, config.min_samples);
+            }
+            other => panic!("Expected bench command, got {other:?}"),
+        }
+        assert!(parse("bench 4 --budget").is_err());
+        assert!(parse("run 4 --budget 2s").is_err());
+    }
+
+    #[test]
+    fn test_baseline() {
+        match parse("baseline save all --budget 500ms") {
+            Ok(Command::Baseline {
+                run,
+                config,
+                file,
+                action,
+            }) => {
+                assert_eq!(registered_days(), run.days);
+                assert_eq!(Duration::from_millis(500), config.budget);
+                assert_eq!(PathBuf::from("baseline.txt"), file);
+                assert_eq!(BaselineAction::Save, action);
+            }
+            other => panic!("Expected baseline command, got {other:?}"),
+        }
+
+        match parse("baseline compare 4 8 --file base.txt --threshold 25 --limit 1s --limit 8=5s") {
+            Ok(Command::Baseline { file, action, .. }) => {
+                let mut limits = TimeLimits::default();
+                limits.add("1s").unwrap();
+                limits.add("8=5s").unwrap();
+                assert_eq!(PathBuf::from("base.txt"), file);
+                assert_eq!(
+                    BaselineAction::Compare {
+                        threshold: 0.25,
+                        limits
+                    },
+                    action
+                );
+            }
+            other => panic!("Expected baseline command, got {other:?}"),
+        }
+
+        match parse("baseline compare 10") {
+            Ok(Command::Baseline { action, .. }) => assert_eq!(
+                BaselineAction::Compare {
+                    threshold: 0.1,
+                    limits: TimeLimits::default().or_defaults()
+                },
+                action
+            ),
+            other => panic!("Expected baseline command, got {other:?}"),
+        }
+
+        assert!(parse("baseline 4").is_err());
+        assert!(parse("baseline save 4 --limit 1s").is_err());
+        assert!(parse("baseline compare 4 --limit 1x").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            Ok(Command::Verify {
+                days: registered_days(),
+                input_dir: None
+            }),
+            parse("verify")
+        );
+        assert_eq!(
+            Ok(Command::Verify {
+                days: vec![9],
+                input_dir: Some(PathBuf::from("puzzles"))
+            }),
+            parse("verify 9 --input-dir puzzles")
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        match parse("compare 2 10 -p 2") {
+            Ok(Command::Compare { run }) => {
+                assert_eq!(vec![2, 10], run.days);
+                assert_eq!(vec![Part::Two], run.parts);
+            }
+            other => panic!("Expected compare command, got {other:?}"),
+        }
+        assert!(parse("compare").is_err());
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            Ok(Command::Answer {
+                day: 9,
+                part: Part::Two,
+                record: Record::TooHigh(2859243744)
+            }),
+            parse("answer 9 2 high 2859243744")
+        );
+        assert!(parse("answer 9 2 high").is_err());
+        assert!(parse("answer 9 2 high 1 2").is_err());
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            Ok(Command::Examples {
+                day: 9,
+                dir: PathBuf::from("puzzles"),
+                check: false
+            }),
+            parse("examples 9")
+        );
+        assert_eq!(
+            Ok(Command::Examples {
+                day: 9,
+                dir: PathBuf::from("saved"),
+                check: true
+            }),
+            parse("examples --check 9 --dir saved")
+        );
+        assert!(parse("examples").is_err());
+        assert!(parse("examples 9 10").is_err());
+    }
+
+    #[test]
+    fn test_fuzz() {
+        assert_eq!(
+            Ok(Command::Fuzz {
+                days: registered_days(),
+                iterations: 10000,
+                seed: 1
+            }),
+            parse("fuzz")
+        );
+        assert_eq!(
+            Ok(Command::Fuzz {
+                days: vec![6, 10, 12],
+                iterations: 500,
+                seed: 3
+            }),
+            parse("fuzz 12 6 10 12 --iterations 500 --seed 3")
+        );
+        assert!(parse("fuzz --iterations many").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        assert_eq!(
+            Ok(Command::Generate {
+                day: 8,
+                size: None,
+                seed: 1,
+                output: None
+            }),
+            parse("generate 8")
+        );
+        assert_eq!(
+            Ok(Command::Generate {
+                day: 8,
+                size: Some(100000),
+                seed: 7,
+                output: Some(PathBuf::from("inputs/day8.big.txt"))
+            }),
+            parse("generate 8 --size 100000 --seed 7 -o inputs/day8.big.txt")
+        );
+        assert!(parse("generate 8 --size -1").is_err());
+    }
+
+    #[test]
+    fn test_new() {
+        let day = (1..=MAX_DAY)
+            .find(|d| !registered_days().contains(d))
+            .unwrap();
+        assert_eq!(
+            Ok(Command::New {
+                day,
+                dir: PathBuf::from("puzzles")
+            }),
+            parse(&format!("new {day}"))
+        );
+        assert!(parse("new").is_err());
+        assert!(parse("new 1").is_err());
+        assert!(parse("new 26").is_err());
+    }
+
+    #[test]
+    fn test_watch() {
+        match parse("watch 10 -p 2 -n alt") {
+            Ok(Command::Watch { run }) => {
+                assert_eq!(vec![10], run.days);
+                assert_eq!(vec![Part::Two], run.parts);
+                assert_eq!(Some("alt".to_string()), run.input_name);
+            }
+            other => panic!("Expected watch command, got {other:?}"),
+        }
+        assert!(parse("watch 1-3").is_err());
+        assert!(parse("watch 10 -j 2").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("0").is_err());
+        assert!(parse("26").is_err());
+        assert!(parse("1 -p 3").is_err());
+        assert!(parse("1 2 --input example.txt").is_err());
+        assert!(parse("1 --bogus").is_err());
+    }
+}
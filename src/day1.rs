@@ -1,15 +1,17 @@
-use std::fs;
-use crate::timed::timed;
+use crate::parse::{ParseError, parse_number};
+use crate::solution::Solution;
 
-fn parse_input(input: &str) -> Vec<i32> {
+fn parse_input(input: &str) -> Result<Vec<i32>, ParseError> {
     input
         .lines()
         .map(|l| l.trim())
         .map(|l| {
-            let mult = if l.starts_with("L") { -1 } else { 1 };
-            l[1..]
-                .parse::<i32>()
-                .expect("could not parse input") * mult
+            let mult = match l.get(..1) {
+                Some("L") => -1,
+                Some("R") => 1,
+                _ => return Err(ParseError::new(input, l, "Expected a rotation like `L68`")),
+            };
+            Ok(parse_number::<i32>(input, &l[1..])? * mult)
         })
         .collect()
 }
@@ -17,14 +19,14 @@ fn parse_input(input: &str) -> Vec<i32> {
 pub fn part1(inputs: &Vec<i32>, start: i32) -> i32 {
     let mut current = start;
     let mut num_zero = 0;
-    
+
     for &i in inputs {
         current = (current + i) % 100;
         if current == 0 {
             num_zero += 1;
         }
     }
-    
+
     num_zero
 }
 
@@ -45,15 +47,33 @@ pub fn part2(inputs: &Vec<i32>, start: i32) -> i32 {
     num_zero
 }
 
-pub fn day1() {
-    let input = fs::read_to_string("inputs/day1.txt").expect("Could not read input");
-
-    let inputs = timed(|| parse_input(&input));
+pub struct Day1 {
+    pub start: i32,
+}
 
-    println!("Part 1: {}", timed(|| part1(&inputs, 50)));
-    println!("Part 2: {}", timed(|| part2(&inputs, 50)));
+impl Default for Day1 {
+    fn default() -> Self {
+        Self { start: 50 }
+    }
 }
 
+impl Solution for Day1 {
+    type Input<'a> = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input, self.start)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input, self.start)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -71,7 +91,7 @@ L1
 L99
 R14
 L82";
-        let inputs = parse_input(&input);
+        let inputs = parse_input(input).unwrap();
         assert_eq!(3, part1(&inputs, 50));
     }
 
@@ -87,7 +107,7 @@ L1
 L99
 R14
 L82";
-        let inputs = parse_input(&input);
+        let inputs = parse_input(input).unwrap();
         assert_eq!(6, part2(&inputs, 50));
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,12 @@
-use crate::timed::{print_timespan, timed};
+use crate::parse::{ParseError, offset};
+use crate::solution::Solution;
 use crate::util::equation_system::gauss_elimination;
-use crate::util::str_util::transpose;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::mem::swap;
-use std::ops::{Add, Mul, Sub};
-use std::rc::Rc;
-use std::simd::{Simd, f64x2, i64x8, u32x8};
-use std::time::Instant;
-use std::{fs, i64, usize};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum Indicator {
+pub enum Indicator {
     On,
     Off,
 }
@@ -31,82 +25,93 @@ type Joltages = Vec<usize>;
 
 type Input = Vec<(Vec<Indicator>, Vec<Vec<usize>>, Vec<usize>)>;
 
-fn read_num(input: &[u8], i: &mut usize) -> usize {
-    let mut num = 0;
-    loop {
-        match input[*i] {
-            c if c >= b'0' && c <= b'9' => num = num * 10 + (c - b'0') as usize,
-            _ => {
-                return num;
-            }
-        }
+/// Byte position in the line and description of a syntax error
+type SyntaxError = (usize, &'static str);
+
+fn read_num(input: &[u8], i: &mut usize) -> Result<usize, SyntaxError> {
+    let start = *i;
+    let mut num: usize = 0;
+    while *i < input.len() && input[*i].is_ascii_digit() {
+        num = num
+            .checked_mul(10)
+            .and_then(|n| n.checked_add((input[*i] - b'0') as usize))
+            .ok_or((start, "Number is too large"))?;
         *i += 1;
     }
+    if *i == start {
+        return Err((start, "Expected a number"));
+    }
+    Ok(num)
 }
 
-fn read_numbers(input: &[u8], i: &mut usize) -> Vec<usize> {
+fn read_numbers(input: &[u8], i: &mut usize) -> Result<Vec<usize>, SyntaxError> {
     let mut res = Vec::new();
     while *i < input.len() {
         match input[*i] {
             b']' | b')' | b'}' => {
                 *i += 1;
-                return res;
+                return Ok(res);
             }
             b',' => {
                 *i += 1;
             }
             _ => {
-                res.push(read_num(input, i));
+                res.push(read_num(input, i)?);
             }
         };
     }
-    panic!("No closing character")
+    Err((*i, "No closing character"))
 }
 
-fn parse_input(input: &str) -> Input {
-    input
-        .lines()
-        .map(|l| {
-            let mut indicators = None;
-            let mut buttons = Vec::new();
-            let mut joltage_req = None;
-
-            let l = l.as_bytes();
-            let mut i = 0;
-            while i < l.len() {
-                match l[i] {
-                    b'[' => {
-                        let mut v = Vec::new();
-                        i += 1;
-                        while l[i] != b']' {
-                            v.push(match l[i] {
-                                b'.' => Indicator::Off,
-                                b'#' => Indicator::On,
-                                _ => panic!("Weird input {}", l[i] as char),
-                            });
-                            i += 1;
-                        }
-                        indicators = Some(v);
-                    }
-                    b'(' => {
-                        i += 1;
-                        buttons.push(read_numbers(l, &mut i));
-                    }
-                    b'{' => {
-                        i += 1;
-                        joltage_req = Some(read_numbers(l, &mut i));
-                    }
-                    b' ' => {}
-                    _ => panic!("Weird input {}", l[i] as char),
-                };
+fn parse_line(l: &[u8]) -> Result<(Vec<Indicator>, Buttons, Joltages), SyntaxError> {
+    let mut indicators = None;
+    let mut buttons = Vec::new();
+    let mut joltage_req = None;
+
+    let mut i = 0;
+    while i < l.len() {
+        match l[i] {
+            b'[' => {
+                let mut v = Vec::new();
+                i += 1;
+                while l.get(i) != Some(&b']') {
+                    v.push(match l.get(i) {
+                        Some(b'.') => Indicator::Off,
+                        Some(b'#') => Indicator::On,
+                        Some(_) => return Err((i, "Expected `.` or `#`")),
+                        None => return Err((i, "No closing character")),
+                    });
+                    i += 1;
+                }
+                indicators = Some(v);
+            }
+            b'(' => {
                 i += 1;
+                buttons.push(read_numbers(l, &mut i)?);
             }
+            b'{' => {
+                i += 1;
+                joltage_req = Some(read_numbers(l, &mut i)?);
+            }
+            b' ' => {}
+            _ => return Err((i, "Unexpected character")),
+        };
+        i += 1;
+    }
 
-            (
-                indicators.expect("No indicators"),
-                buttons,
-                joltage_req.expect("No joltage requirement"),
-            )
+    Ok((
+        indicators.ok_or((l.len(), "No indicators"))?,
+        buttons,
+        joltage_req.ok_or((l.len(), "No joltage requirement"))?,
+    ))
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    input
+        .lines()
+        .map(|l| {
+            parse_line(l.as_bytes())
+                .map_err(|(i, message)| ParseError::at(input, offset(input, l) + i, 1, message))
         })
         .collect()
 }
@@ -178,7 +183,7 @@ pub fn part1(input: &Input) -> i64 {
         .sum()
 }
 
-fn is_p2_solution(buttons: &Buttons, joltages: &Joltages, presses: &Vec<f64>) -> bool {
+fn is_p2_solution(buttons: &Buttons, joltages: &Joltages, presses: &[f64]) -> bool {
     let mut result_joltages: Joltages = vec![0; joltages.len()];
 
     for (button_idx, button) in buttons.iter().enumerate() {
@@ -195,7 +200,7 @@ fn is_p2_solution(buttons: &Buttons, joltages: &Joltages, presses: &Vec<f64>) ->
 }
 
 fn part2_line(buttons: &Buttons, joltages: &Joltages) -> i64 {
-    let mut matrix = joltages
+    let matrix: Vec<Vec<f64>> = joltages
         .iter()
         .enumerate()
         .map(|(joltage_index, joltage)| {
@@ -212,7 +217,7 @@ fn part2_line(buttons: &Buttons, joltages: &Joltages) -> i64 {
                 .collect()
         })
         .collect();
-    let mut res = gauss_elimination(&matrix);
+    let res = gauss_elimination(&matrix);
 
     let mut param_max = vec![i64::MAX; res.len() - 1];
     for (param_idx, param) in res[1..].iter().cloned().enumerate() {
@@ -255,9 +260,9 @@ fn part2_line(buttons: &Buttons, joltages: &Joltages) -> i64 {
     let mut param_values = vec![0i64; res.len() - 1];
     let mut min_num_presses = i64::MAX;
     let mut min_v = Vec::new();
-    'main_loop: loop {
+    loop {
         let mut v = res[0].clone();
-        'param_loop: for param_index in 0..param_values.len() {
+        for param_index in 0..param_values.len() {
             for i in 0..v.len() {
                 let factor = param_values[param_index] as f64;
                 v[i] += factor * res[1 + param_index][i];
@@ -290,7 +295,7 @@ fn part2_line(buttons: &Buttons, joltages: &Joltages) -> i64 {
                 break;
             }
         }
-        if param_values.len() == 0 {
+        if param_values.is_empty() {
             panic!("Should not be here");
         }
     }
@@ -304,18 +309,31 @@ pub fn part2(input: &Input) -> i64 {
     num_presses.iter().sum()
 }
 
-pub fn day10() {
-    let input = fs::read_to_string("inputs/day10.txt").expect("Could not read input");
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Input;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    let inputs = timed(|| parse_input(&input));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::Rng;
+    use crate::util::property::{Case, differential};
 
     const TEST_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
     [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
@@ -323,41 +341,203 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(7, part1(&input));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(33, part2(&input));
     }
 
     #[test]
     fn test_p2_2() {
-        let input = parse_input(&"[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
+        let input =
+            parse_input("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}").unwrap();
         assert_eq!(12, part2(&input));
     }
 
     #[test]
     fn test_p2_3() {
         let input = parse_input(
-            &"[..##..] (0,5) (1,2,3,4,5) (1,3,4,5) (3,4) (2,3,5) (0,1,2,5) {29,40,23,42,39,52}",
-        );
+            "[..##..] (0,5) (1,2,3,4,5) (1,3,4,5) (3,4) (2,3,5) (0,1,2,5) {29,40,23,42,39,52}",
+        )
+        .unwrap();
         assert_eq!(part2(&input), 71);
     }
 
     #[test]
     fn test_p2_4() {
         let input = parse_input(
-            &"[..#...#.#] (2,7) (1,4,7) (0,1,3,4,5,6,8) (2,3,4,5,6,7,8) (1,4,6,7) (0,2,4,5,6,7,8) (0,5,7) (0,1,3,5,6,7,8) (0,4,6) (0,1,2,5,6,7,8) (0,1,2,3,5) {237,230,49,207,213,228,221,72,200}",
-        );
+            "[..#...#.#] (2,7) (1,4,7) (0,1,3,4,5,6,8) (2,3,4,5,6,7,8) (1,4,6,7) (0,2,4,5,6,7,8) (0,5,7) (0,1,3,5,6,7,8) (0,4,6) (0,1,2,5,6,7,8) (0,1,2,3,5) {237,230,49,207,213,228,221,72,200}",
+        ).unwrap();
         assert_eq!(part2(&input), 267);
     }
 
     #[test]
     fn test_p2_5() {
-        let input = parse_input(&"[####] (1,3) (2,3) (3) (0,1,2) (0,2) (0,1) {21,23,21,27}");
+        let input =
+            parse_input("[####] (1,3) (2,3) (3) (0,1,2) (0,2) (0,1) {21,23,21,27}").unwrap();
         assert_eq!(part2(&input), 48);
     }
+
+    /// A machine with the presses that reach its joltages, so it always has a
+    /// solution.
+    #[derive(Clone, Debug)]
+    struct Machine {
+        lights: usize,
+        buttons: Buttons,
+        presses: Vec<usize>,
+    }
+
+    impl Machine {
+        fn random(rng: &mut Rng) -> Self {
+            let lights = rng.range(2, 5) as usize;
+            let num_buttons = rng.range(2, 6) as usize;
+            let mut buttons: Buttons = (0..num_buttons)
+                .map(|_| {
+                    let mut button: Vec<usize> = (0..lights).filter(|_| rng.chance(0.4)).collect();
+                    if button.is_empty() {
+                        button.push(rng.index(lights));
+                    }
+                    button
+                })
+                .collect();
+            for light in 0..lights {
+                if !buttons.iter().any(|b| b.contains(&light)) {
+                    let button = &mut buttons[rng.index(num_buttons)];
+                    button.push(light);
+                    button.sort();
+                }
+            }
+            let mut presses: Vec<usize> = (0..num_buttons).map(|_| rng.below(5) as usize).collect();
+            if presses.iter().all(|&p| p == 0) {
+                presses[0] = 1;
+            }
+            Self {
+                lights,
+                buttons,
+                presses,
+            }
+        }
+
+        /// Every light has a button and some button is pressed
+        fn is_valid(&self) -> bool {
+            (0..self.lights).all(|l| self.buttons.iter().any(|b| b.contains(&l)))
+                && self.presses.iter().any(|&p| p > 0)
+        }
+    }
+
+    impl Case for Machine {
+        fn input(&self) -> String {
+            let mut joltages = vec![0; self.lights];
+            for (button, &presses) in self.buttons.iter().zip(&self.presses) {
+                for &light in button {
+                    joltages[light] += presses;
+                }
+            }
+            let list = |values: &[usize]| {
+                let values: Vec<_> = values.iter().map(usize::to_string).collect();
+                values.join(",")
+            };
+            let buttons: Vec<_> = self
+                .buttons
+                .iter()
+                .map(|b| format!("({})", list(b)))
+                .collect();
+            format!(
+                "[{}] {} {{{}}}",
+                ".".repeat(self.lights),
+                buttons.join(" "),
+                list(&joltages)
+            )
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.buttons.len() {
+                let mut machine = self.clone();
+                machine.buttons.remove(i);
+                machine.presses.remove(i);
+                smaller.push(machine);
+            }
+            for light in 0..self.lights {
+                let mut machine = self.clone();
+                machine.lights -= 1;
+                for button in &mut machine.buttons {
+                    button.retain(|&l| l != light);
+                    button
+                        .iter_mut()
+                        .filter(|l| **l > light)
+                        .for_each(|l| *l -= 1);
+                }
+                for i in (0..machine.buttons.len()).rev() {
+                    if machine.buttons[i].is_empty() {
+                        machine.buttons.remove(i);
+                        machine.presses.remove(i);
+                    }
+                }
+                smaller.push(machine);
+            }
+            for i in 0..self.presses.len() {
+                for presses in [0, self.presses[i] / 2, self.presses[i].saturating_sub(1)] {
+                    if presses < self.presses[i] {
+                        let mut machine = self.clone();
+                        machine.presses[i] = presses;
+                        smaller.push(machine);
+                    }
+                }
+            }
+            smaller.retain(Machine::is_valid);
+            smaller
+        }
+    }
+
+    /// Tries every number of presses of every button.
+    fn fewest_presses_naive(buttons: &[Vec<usize>], remaining: &mut Joltages) -> Option<usize> {
+        let Some((button, rest)) = buttons.split_first() else {
+            return remaining.iter().all(|&j| j == 0).then_some(0);
+        };
+        let max = button.iter().map(|&l| remaining[l]).min().unwrap_or(0);
+        let mut fewest = None;
+        for presses in 0..=max {
+            if let Some(rest_presses) = fewest_presses_naive(rest, remaining) {
+                fewest = Some(fewest.unwrap_or(usize::MAX).min(presses + rest_presses));
+            }
+            if presses < max {
+                button.iter().for_each(|&l| remaining[l] -= 1);
+            }
+        }
+        button.iter().for_each(|&l| remaining[l] += max);
+        fewest
+    }
+
+    #[test]
+    fn test_p2_matches_naive() {
+        differential(
+            200,
+            Machine::random,
+            |input| part2(&parse_input(input).unwrap()),
+            |input| {
+                parse_input(input)
+                    .unwrap()
+                    .iter()
+                    .map(|(_, buttons, joltages)| {
+                        fewest_presses_naive(buttons, &mut joltages.clone()).unwrap() as i64
+                    })
+                    .sum()
+            },
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("[.##.] (3) (1,3) {3,5,4,7}\n[...#.] (0,2,3,4) {7,5").unwrap_err();
+        assert_eq!((2, 23), (error.line, error.column));
+        assert_eq!("No closing character", error.message);
+
+        let error = parse_input("[.#x.] (3) {3}").unwrap_err();
+        assert_eq!((1, 4), (error.line, error.column));
+    }
 }
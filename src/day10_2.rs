@@ -1,196 +1,156 @@
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum Indicator {
-    On,
-    Off,
+//! Brute force for day 10 part 2, registered as an alternate of the solver in
+//! `day10`. It searches through every number of presses of every button, so
+//! it only takes machines with up to [`MAX_COMBINATIONS`] combinations of
+//! presses. Real inputs have machines with far more.
+
+/// Upper bound on the combinations of presses of a single machine
+pub const MAX_COMBINATIONS: u64 = 1 << 40;
+
+/// Indicators, buttons and joltages, as parsed by `day10`
+type Machine<I> = (I, Vec<Vec<usize>>, Vec<usize>);
+
+/// How often each button can be pressed at most, one more than the lowest
+/// joltage it raises.
+fn max_presses(buttons: &[Vec<usize>], joltages: &[usize]) -> Vec<u64> {
+    buttons
+        .iter()
+        .map(|b| {
+            b.iter()
+                .filter_map(|&joltage_index| joltages.get(joltage_index))
+                .map(|&j| j as u64 + 1)
+                .min()
+                .unwrap_or(1)
+        })
+        .collect()
 }
 
-impl Indicator {
-    pub fn toggle(&mut self) {
-        *self = match self {
-            Indicator::Off => Indicator::On,
-            Indicator::On => Indicator::Off,
-        };
-    }
+/// Number of combinations of presses, `None` if it does not fit in a `u64`.
+fn combinations(buttons: &[Vec<usize>], joltages: &[usize]) -> Option<u64> {
+    max_presses(buttons, joltages)
+        .into_iter()
+        .try_fold(1u64, |a, p| a.checked_mul(p))
 }
 
-type Input = Vec<(Vec<Indicator>, Vec<Vec<usize>>, Vec<usize>)>;
+/// Tries every number of presses of the buttons before `k`, from the last
+/// one down, adding to `presses` and lowering `remaining` as it goes. Stops
+/// early when a joltage cannot be reached any more or the presses cannot
+/// beat `best`.
+fn search(buttons: &[Vec<usize>], k: usize, remaining: &mut [usize], presses: i64, best: &mut i64) {
+    // Every press lowers a joltage by at most one
+    let most = remaining.iter().copied().max().unwrap_or(0) as i64;
+    if presses + most >= *best {
+        return;
+    }
+    if k == 0 {
+        if most == 0 {
+            *best = presses;
+        }
+        return;
+    }
 
-fn read_num(input: &[u8], i: &mut usize) -> usize {
-    let mut num = 0;
-    loop {
-        match input[*i] {
-            c if c >= b'0' && c <= b'9' => num = num * 10 + (c - b'0') as usize,
-            _ => {
-                return num;
+    let button = &buttons[k - 1];
+    let earlier = &buttons[..k - 1];
+    let mut max = button.iter().map(|&j| remaining[j]).min().unwrap_or(0);
+    let mut min = 0;
+    for (j, &r) in remaining.iter().enumerate() {
+        if r > 0 && !earlier.iter().any(|b| b.contains(&j)) {
+            if !button.contains(&j) {
+                return;
             }
+            // Only this button is left to reach joltage `j`
+            min = min.max(r);
+            max = max.min(r);
         }
-        *i += 1;
     }
-}
 
-fn read_numbers(input: &[u8], i: &mut usize) -> Vec<usize> {
-    let mut res = Vec::new();
-    while *i < input.len() {
-        match input[*i] {
-            b']' | b')' | b'}' => {
-                *i += 1;
-                return res;
-            }
-            b',' => {
-                *i += 1;
-            }
-            _ => {
-                res.push(read_num(input, i));
-            }
-        };
+    for n in (min..=max).rev() {
+        for &j in button {
+            remaining[j] -= n;
+        }
+        search(buttons, k - 1, remaining, presses + n as i64, best);
+        for &j in button {
+            remaining[j] += n;
+        }
     }
-    panic!("No closing character")
 }
 
-fn parse_input(input: &str) -> Input {
-    input
-        .lines()
-        .map(|l| {
-            let mut indicators = None;
-            let mut buttons = Vec::new();
-            let mut joltage_req = None;
-
-            let l = l.as_bytes();
-            let mut i = 0;
-            while i < l.len() {
-                match l[i] {
-                    b'[' => {
-                        let mut v = Vec::new();
-                        i += 1;
-                        while l[i] != b']' {
-                            v.push(match l[i] {
-                                b'.' => Indicator::Off,
-                                b'#' => Indicator::On,
-                                _ => panic!("Weird input {}", l[i] as char),
-                            });
-                            i += 1;
-                        }
-                        indicators = Some(v);
-                    }
-                    b'(' => {
-                        i += 1;
-                        buttons.push(read_numbers(l, &mut i));
-                    }
-                    b'{' => {
-                        i += 1;
-                        joltage_req = Some(read_numbers(l, &mut i));
-                    }
-                    b' ' => {}
-                    _ => panic!("Weird input {}", l[i] as char),
-                };
-                i += 1;
-            }
-
-            (
-                indicators.expect("No indicators"),
-                buttons,
-                joltage_req.expect("No joltage requirement"),
-            )
-        })
-        .collect()
+fn fewest_presses(buttons: &[Vec<usize>], joltages: &[usize]) -> i64 {
+    let mut best = i64::MAX;
+    search(buttons, buttons.len(), &mut joltages.to_vec(), 0, &mut best);
+    best
 }
 
-fn part2(input: &Input) -> i64 {
-    let mut num_presses = 0;
-    for (_, buttons, joltages) in input {
-        println!("One row");
-        let max_presses: Vec<usize> = buttons
-            .iter()
-            .map(|b| {
-                b.iter()
-                    .map(|&joltage_index| joltages[joltage_index] + 1)
-                    .filter(|&j| j > 0)
-                    .min()
-                    .unwrap()
-            })
-            .collect();
-
-        let total_presses: usize = max_presses
-            .iter()
-            .copied()
-            .reduce(|a, p| a.max(1) * p.max(1))
-            .unwrap();
-        let mut min_num_presses = i64::MAX;
-
-        let mut press = 1;
-        'press: while press < total_presses {
-            let mut current_joltages = vec![0; joltages.len()];
-            let mut remaining_presses = press;
-            let mut current_num_presses = 0;
-            let mut button_factor = 1;
-            for btn_idx in 0..buttons.len() {
-                let btn_max_presses = max_presses[btn_idx];
-                let num_presses = remaining_presses % btn_max_presses;
-                remaining_presses = remaining_presses / btn_max_presses;
-                current_num_presses += num_presses;
-
-                for &joltage_index in &buttons[btn_idx] {
-                    current_joltages[joltage_index] += num_presses;
-                    if current_joltages[joltage_index] > joltages[joltage_index] {
-                        press += ((btn_max_presses - num_presses) * button_factor).max(1);
-                        continue 'press;
-                    }
-                }
-            }
-
-            if current_joltages
-                .iter()
-                .zip(joltages)
-                .all(|(j0, j1)| *j0 == *j1)
-            {
-                min_num_presses = min_num_presses.min(current_num_presses as i64);
-            }
-            press += 1;
-        }
-        num_presses += min_num_presses;
+/// Sum of the fewest presses of every machine, `i64::MAX` if a machine
+/// cannot reach its joltages. `None` if a machine has more than
+/// [`MAX_COMBINATIONS`] combinations. The indicators are not looked at.
+pub fn part2<I>(input: &[Machine<I>]) -> Option<i64> {
+    if input.iter().any(|(_, buttons, joltages)| {
+        combinations(buttons, joltages).is_none_or(|c| c > MAX_COMBINATIONS)
+    }) {
+        return None;
     }
-    num_presses
+
+    Some(
+        input
+            .iter()
+            .map(|(_, buttons, joltages)| fewest_presses(buttons, joltages))
+            .fold(0, i64::saturating_add),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day10::Day10;
+    use crate::solution::Solution;
 
     const TEST_INPUT: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
     [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
     [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
 
+    fn solve(input: &str) -> Option<i64> {
+        part2(&Day10.parse(input).unwrap())
+    }
+
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
-        assert_eq!(33, part2(&input));
+        assert_eq!(Some(33), solve(TEST_INPUT));
     }
 
     #[test]
     fn test_p2_2() {
-        let input = parse_input(&"[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}");
-        assert_eq!(12, part2(&input));
+        assert_eq!(
+            Some(12),
+            solve("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}")
+        );
     }
 
     #[test]
     fn test_p2_3() {
-        let input = parse_input(
-            &"[..##..] (0,5) (1,2,3,4,5) (1,3,4,5) (3,4) (2,3,5) (0,1,2,5) {29,40,23,42,39,52}",
+        assert_eq!(
+            Some(71),
+            solve(
+                "[..##..] (0,5) (1,2,3,4,5) (1,3,4,5) (3,4) (2,3,5) (0,1,2,5) {29,40,23,42,39,52}"
+            )
         );
-        assert_eq!(part2(&input), 71);
     }
 
     #[test]
     fn test_p2_4() {
-        let input = parse_input(
-            &"[..#...#.#] (2,7) (1,4,7) (0,1,3,4,5,6,8) (2,3,4,5,6,7,8) (1,4,6,7) (0,2,4,5,6,7,8) (0,5,7) (0,1,3,5,6,7,8) (0,4,6) (0,1,2,5,6,7,8) (0,1,2,3,5) {237,230,49,207,213,228,221,72,200}",
+        // Beyond the limit, day10 checks the answer of 267
+        assert_eq!(
+            None,
+            solve(
+                "[..#...#.#] (2,7) (1,4,7) (0,1,3,4,5,6,8) (2,3,4,5,6,7,8) (1,4,6,7) (0,2,4,5,6,7,8) (0,5,7) (0,1,3,5,6,7,8) (0,4,6) (0,1,2,5,6,7,8) (0,1,2,3,5) {237,230,49,207,213,228,221,72,200}"
+            )
         );
-        assert_eq!(part2(&input), 267);
     }
 
     #[test]
     fn test_p2_5() {
-        let input = parse_input(&"[####] (1,3) (2,3) (3) (0,1,2) (0,2) (0,1) {21,23,21,27}");
-        assert_eq!(part2(&input), 48);
+        assert_eq!(
+            Some(48),
+            solve("[####] (1,3) (2,3) (3) (0,1,2) (0,2) (0,1) {21,23,21,27}")
+        );
     }
 }
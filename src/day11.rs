@@ -1,16 +1,23 @@
-use std::{collections::VecDeque, fs};
+use std::collections::VecDeque;
 
 use rustc_hash::FxHashMap;
 
-use crate::timed::timed;
+use crate::parse::ParseError;
+use crate::solution::Solution;
 
 type Input<'a> = (NodeList<'a>, FxHashMap<usize, Vec<usize>>);
 
-struct NodeList<'a> {
+pub struct NodeList<'a> {
     nodes: Vec<&'a str>,
     indices: FxHashMap<&'a str, usize>,
 }
 
+impl<'a> Default for NodeList<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> NodeList<'a> {
     pub fn new() -> Self {
         Self {
@@ -26,21 +33,25 @@ impl<'a> NodeList<'a> {
                 let idx = self.nodes.len();
                 self.nodes.push(node);
                 self.indices.insert(node, idx);
-                return idx;
+                idx
             }
         }
     }
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input<'_>, ParseError> {
     let mut nodes = NodeList::new();
     let mut edges = Vec::new();
 
     for line in input.lines() {
-        let mut src_targets = line.splitn(2, ":");
-        let src = src_targets.next().unwrap();
+        let (src, targets) = line.split_once(":").ok_or_else(|| {
+            ParseError::new(
+                input,
+                line,
+                "Expected a device and its outputs like `aaa: bbb ccc`",
+            )
+        })?;
         let src_index = nodes.get_or_add_index(src);
-        let targets = src_targets.next().unwrap();
         for target in targets.split_whitespace() {
             let target_index = nodes.get_or_add_index(target);
             edges.push((src_index, target_index));
@@ -56,7 +67,7 @@ fn parse_input(input: &str) -> Input {
             .or_insert(vec![to]);
     }
 
-    (nodes, edges_by_node)
+    Ok((nodes, edges_by_node))
 }
 
 fn num_paths_between((nodes, edges_by_node): &Input, start: &str, end: &str) -> i64 {
@@ -100,22 +111,33 @@ pub fn part2(input: &Input) -> i64 {
     if fft_dac > 0 {
         let svr_fft = num_paths_between(input, "svr", "fft");
         let dac_out = num_paths_between(input, "dac", "out");
-        (svr_fft * fft_dac * dac_out)
+        svr_fft * fft_dac * dac_out
     } else {
         let dac_fft = num_paths_between(input, "dac", "fft");
         let svr_dac = num_paths_between(input, "svr", "dac");
         let fft_out = num_paths_between(input, "fft", "out");
-        (svr_dac * dac_fft * fft_out)
+        svr_dac * dac_fft * fft_out
     }
 }
 
-pub fn day11() {
-    let input = fs::read_to_string("inputs/day11.txt").expect("Could not read input");
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Input<'a>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let inputs = timed(|| parse_input(&input));
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -135,18 +157,19 @@ iii: out";
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT_P1);
+        let input = parse_input(TEST_INPUT_P1).unwrap();
         assert_eq!(5, part1(&input));
     }
 
     #[test]
     fn test_p1_2() {
         let input = parse_input(
-            &"you: a c
+            "you: a c
 a: b
 b: c
 c: out",
-        );
+        )
+        .unwrap();
         assert_eq!(2, part1(&input));
     }
 
@@ -166,7 +189,7 @@ hhh: out";
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT_P2);
+        let input = parse_input(TEST_INPUT_P2).unwrap();
         assert_eq!(2, part2(&input));
     }
 }
@@ -1,29 +1,42 @@
-use std::{collections::VecDeque, fs, str::Lines};
-
-use rustc_hash::FxHashMap;
-
-use crate::{timed::timed, util::grid::Grid};
-
-#[derive(Clone, Copy, PartialEq, Eq)]
+use std::str::Lines;
+
+use crate::{
+    parse::{ParseError, check_grid, parse_number},
+    solution::{Part, Solution},
+    util::{
+        grid::{Grid, GridChar},
+        vec2::Vec2,
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum GridEntry {
     Free,
     Occupied,
 }
 
-impl std::fmt::Debug for GridEntry {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl GridChar for GridEntry {
+    fn to_char(&self) -> char {
         match self {
-            Self::Free => write!(f, "."),
-            Self::Occupied => write!(f, "#"),
+            Self::Free => '.',
+            Self::Occupied => '#',
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Free),
+            '#' => Some(Self::Occupied),
+            _ => None,
         }
     }
 }
 
-struct Shape {
+pub struct Shape {
     grid: Grid<GridEntry>,
 }
 
-struct Region {
+pub struct Region {
     width: usize,
     height: usize,
     num_shapes: Vec<usize>,
@@ -31,138 +44,188 @@ struct Region {
 
 type Input<'a> = (Vec<Shape>, Vec<Region>);
 
-fn read_shape(lines: &mut Lines<'_>) -> Shape {
+fn read_shape(input: &str, lines: &mut Lines<'_>) -> Result<Shape, ParseError> {
     let mut grid_str = Vec::new();
 
-    loop {
-        if let Some(l) = lines.next() {
-            if l.is_empty() {
-                break;
-            }
-            grid_str.push(l);
-        } else {
+    for l in lines.by_ref() {
+        if l.is_empty() {
             break;
         }
+        grid_str.push(l);
     }
 
-    let grid = Grid::from_lines(grid_str.iter().copied(), |s| match s {
-        '#' => GridEntry::Occupied,
-        '.' => GridEntry::Free,
-        _ => panic!("Unrecognized input"),
+    check_grid(input, grid_str.iter().copied(), "#.")?;
+    let grid = Grid::from_lines(grid_str.iter().copied(), |c| {
+        GridEntry::from_char(c).expect("Checked by check_grid")
     });
 
-    Shape { grid }
-}
-
-fn read_num(input: &[u8], i: &mut usize) -> usize {
-    let mut num = 0;
-    loop {
-        match input[*i] {
-            c if c >= b'0' && c <= b'9' => num = num * 10 + (c - b'0') as usize,
-            _ => {
-                return num;
-            }
-        }
-        *i += 1;
-        if *i == input.len() {
-            return num;
-        }
-    }
+    Ok(Shape { grid })
 }
 
-fn parse_region(line: &str) -> Region {
-    let b = line.as_bytes();
-
-    let mut i = 0;
-    let width = read_num(b, &mut i);
-    i += 1;
-    let height = read_num(b, &mut i);
-    i += 2;
+fn parse_region(input: &str, line: &str) -> Result<Region, ParseError> {
+    let error = || ParseError::new(input, line, "Expected a region like `12x5: 1 0 1 0 3 2`");
+    let (size, shapes) = line.split_once(": ").ok_or_else(error)?;
+    let (width, height) = size.split_once("x").ok_or_else(error)?;
 
-    let mut num_shapes = Vec::new();
+    let num_shapes = shapes
+        .split_whitespace()
+        .map(|n| parse_number(input, n))
+        .collect::<Result<_, _>>()?;
 
-    while i < b.len() {
-        num_shapes.push(read_num(b, &mut i));
-        i += 1;
-    }
-
-    Region {
-        height,
-        width,
+    Ok(Region {
+        height: parse_number(input, height)?,
+        width: parse_number(input, width)?,
         num_shapes,
-    }
+    })
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_input(input: &str) -> Result<Input<'_>, ParseError> {
     let mut shapes = Vec::new();
     let mut regions = Vec::new();
 
     let mut lines = input.lines();
     while let Some(line) = lines.next() {
         if line.ends_with(":") {
-            shapes.push(read_shape(&mut lines));
+            shapes.push(read_shape(input, &mut lines)?);
         } else {
-            regions.push(parse_region(line));
+            regions.push(parse_region(input, line)?);
             // while let Some(line) = lines.next() {
             //     regions.push(parse_region(line));
             // }
         }
     }
 
-    (shapes, regions)
+    Ok((shapes, regions))
 }
 
-pub fn part1(input: &Input) -> i64 {
-    let mut num_big_enough = 0;
-    let mut num_possible = 0;
-    let shape_areas: Vec<usize> = input
-        .0
+/// Cells of every orientation of a shape, relative to its first occupied
+/// cell in reading order.
+fn orientations(shape: &Shape) -> Vec<Vec<Vec2<i64>>> {
+    shape
+        .grid
+        .orientations()
         .iter()
-        .map(|s| {
-            s.grid
+        .map(|grid| {
+            let cells: Vec<_> = grid
                 .iter()
-                .filter(|(_, f)| **f == GridEntry::Occupied)
-                .count()
+                .filter(|(_, e)| **e == GridEntry::Occupied)
+                .map(|(p, _)| p)
+                .collect();
+            let first = cells.first().copied().unwrap_or(Vec2::new(0, 0));
+            cells.into_iter().map(|p| p - first).collect()
         })
-        .collect();
-
-    for (i, region) in input.1.iter().enumerate() {
-        let num_shapes: usize = region.num_shapes.iter().copied().sum();
-        let shape_area: usize = region
-            .num_shapes
-            .iter()
-            .copied()
-            .enumerate()
-            .map(|(i, num)| shape_areas[i] * num)
-            .sum();
-
-        let shape_rect_area = num_shapes * 9;
-        let region_area = region.width * region.height;
-
-        let fit_in_area = shape_area < region_area;
-        let fit_in_rect_area = shape_rect_area <= region_area;
-
-        if fit_in_rect_area {
-            num_big_enough += 1;
+        .collect()
+}
+
+/// Fills the free cells from `cell` on in reading order, either with the first
+/// cell of a shape that is still `remaining` or by leaving one of the `free`
+/// cells empty.
+fn pack(
+    region: &mut Grid<GridEntry>,
+    shapes: &[Vec<Vec<Vec2<i64>>>],
+    remaining: &mut [usize],
+    cell: usize,
+    free: usize,
+) -> bool {
+    if remaining.iter().all(|&n| n == 0) {
+        return true;
+    }
+    let Some(i) = (cell..region.cells.len()).find(|&i| region.cells[i] == GridEntry::Free) else {
+        return false;
+    };
+    let (x, y) = ((i % region.width) as i64, (i / region.width) as i64);
+
+    for (s, orientations) in shapes.iter().enumerate() {
+        if remaining[s] == 0 {
+            continue;
         }
-        if fit_in_area {
-            num_possible += 1;
+        for cells in orientations {
+            if !cells
+                .iter()
+                .all(|c| region.at(x + c.x, y + c.y) == Some(GridEntry::Free))
+            {
+                continue;
+            }
+            for c in cells {
+                region.update(x + c.x, y + c.y, GridEntry::Occupied);
+            }
+            remaining[s] -= 1;
+            let packed = pack(region, shapes, remaining, i + 1, free);
+            remaining[s] += 1;
+            for c in cells {
+                region.update(x + c.x, y + c.y, GridEntry::Free);
+            }
+            if packed {
+                return true;
+            }
         }
     }
-    num_big_enough
+
+    free > 0 && pack(region, shapes, remaining, i + 1, free - 1)
+}
+
+fn fits(region: &Region, shapes: &[Vec<Vec<Vec2<i64>>>]) -> bool {
+    let area = region.width * region.height;
+    let needed: usize = region
+        .num_shapes
+        .iter()
+        .zip(shapes)
+        .map(|(&n, orientations)| n * orientations.first().map_or(0, Vec::len))
+        .sum();
+    if needed > area {
+        return false;
+    }
+
+    // Shapes are at most 3x3, so they fit side by side without searching
+    let num_shapes: usize = region.num_shapes.iter().sum();
+    if (region.width / 3) * (region.height / 3) >= num_shapes {
+        return true;
+    }
+
+    // Filling short rows leaves fewer gaps to try
+    let (width, height) = if region.width <= region.height {
+        (region.width, region.height)
+    } else {
+        (region.height, region.width)
+    };
+    let mut grid = Grid::new(width, height, GridEntry::Free);
+    let mut remaining = region.num_shapes.clone();
+    pack(&mut grid, shapes, &mut remaining, 0, area - needed)
+}
+
+pub fn part1(input: &Input) -> i64 {
+    let shapes: Vec<_> = input.0.iter().map(orientations).collect();
+    input
+        .1
+        .iter()
+        .filter(|region| fits(region, &shapes))
+        .count() as i64
 }
 
-pub fn part2(input: &Input) -> i64 {
+pub fn part2(_input: &Input) -> i64 {
     0
 }
 
-pub fn day12() {
-    let input = fs::read_to_string("inputs/day12.txt").expect("Could not read input");
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = Input<'a>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const UNFINISHED: &'static [Part] = &[Part::Two];
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    let inputs = timed(|| parse_input(&input));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -203,10 +266,18 @@ mod tests {
 12x5: 1 0 1 0 2 2
 12x5: 1 0 1 0 3 2";
 
+    #[test]
+    fn test_shape_orientations() {
+        let (shapes, _) = parse_input(TEST_INPUT_P1).unwrap();
+        let orientations: Vec<usize> = shapes.iter().map(|s| s.grid.orientations().len()).collect();
+        assert_eq!(vec![8, 8, 2, 4, 4, 2], orientations);
+        assert_eq!("###\n#..\n###", shapes[4].grid.to_string());
+    }
+
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT_P1);
-        assert_eq!(3, part1(&input));
+        let input = parse_input(TEST_INPUT_P1).unwrap();
+        assert_eq!(2, part1(&input));
     }
 
     /*
@@ -1,17 +1,16 @@
-use std::fs;
-use crate::timed::timed;
+use crate::parse::{ParseError, parse_number};
+use crate::solution::Solution;
 
 type Range = (i64, i64);
-fn parse_input(input: &str) -> Vec<Range> {
+fn parse_input(input: &str) -> Result<Vec<Range>, ParseError> {
     input
+        .trim()
         .split(",")
-        .map(|l| l.split_once("-").expect("No separator found"))
-        .map(|(min, max)| {
-            (
-                min.parse()
-                    .expect(&*format!("Could not parse min, {}", min)),
-                max.parse().expect("Could not parse max"),
-            )
+        .map(|l| {
+            let (min, max) = l
+                .split_once("-")
+                .ok_or_else(|| ParseError::new(input, l, "Expected a range like `11-22`"))?;
+            Ok((parse_number(input, min)?, parse_number(input, max)?))
         })
         .collect()
 }
@@ -42,12 +41,11 @@ pub fn part1(ranges: &Vec<Range>) -> i64 {
     sum
 }
 
-// Execution took 231ms
-fn is_repeated_str(current: i64) -> bool {
+pub fn is_repeated_str(current: i64) -> bool {
     let current_str = current.to_string();
 
-    for num_splits in 2..current_str.len()+1 {
-        if current_str.len() % num_splits != 0 {
+    for num_splits in 2..current_str.len() + 1 {
+        if !current_str.len().is_multiple_of(num_splits) {
             continue;
         }
 
@@ -60,29 +58,28 @@ fn is_repeated_str(current: i64) -> bool {
     false
 }
 
-// Execution took 202ms
 fn is_repeated(current: i64) -> bool {
     let current_len = current.ilog10() + 1;
-    for test_len in 0..current_len{
+    for test_len in 0..current_len {
         let test = current % 10i64.pow(test_len);
         if test == 0 || current % test != 0 {
-            continue
+            continue;
         }
         let divisor = current / test;
         let mut expected = 0;
-        for j in 0..current_len/test_len {
+        for j in 0..current_len / test_len {
             expected += 10i64.pow(test_len * j);
         }
 
         if divisor == expected {
-            return true
+            return true;
         }
     }
 
     false
 }
 
-pub fn part2(ranges: &Vec<Range>) -> i64 {
+fn sum_repeated(ranges: &[Range], is_repeated: fn(i64) -> bool) -> i64 {
     let mut sum = 0i64;
 
     for (min, max) in ranges {
@@ -98,13 +95,33 @@ pub fn part2(ranges: &Vec<Range>) -> i64 {
     sum
 }
 
-pub fn day2() {
-    let input = fs::read_to_string("inputs/day2.txt").expect("Could not read input");
+pub fn part2(ranges: &[Range]) -> i64 {
+    sum_repeated(ranges, is_repeated)
+}
+
+/// Part 2 checking the digits as a string, registered as an alternate
+pub fn part2_str(ranges: &[Range]) -> i64 {
+    sum_repeated(ranges, is_repeated_str)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Range>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let inputs = timed(|| parse_input(&input));
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -115,43 +132,49 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let ranges = parse_input(&TEST_INPUT);
+        let ranges = parse_input(TEST_INPUT).unwrap();
         assert_eq!(1227775554, part1(&ranges));
     }
 
     #[test]
     fn test_p1_small_range() {
-        let ranges = parse_input(&"11-22,95-115");
+        let ranges = parse_input("11-22,95-115").unwrap();
         assert_eq!(132, part1(&ranges));
     }
 
     #[test]
     fn test_p1_1010() {
-        let ranges = parse_input(&"998-1012");
+        let ranges = parse_input("998-1012").unwrap();
         assert_eq!(1010, part1(&ranges));
     }
 
     #[test]
     fn test_p1_other_range() {
-        let ranges = parse_input(&"1188511880-1188511890");
+        let ranges = parse_input("1188511880-1188511890").unwrap();
         assert_eq!(1188511885, part1(&ranges));
     }
 
     #[test]
     fn test_p1_no_ranges() {
-        let ranges = parse_input(&"2121212118-2121212124");
+        let ranges = parse_input("2121212118-2121212124").unwrap();
         assert_eq!(0, part1(&ranges));
     }
 
     #[test]
     fn test_p2() {
-        let ranges = parse_input(&TEST_INPUT);
+        let ranges = parse_input(TEST_INPUT).unwrap();
         assert_eq!(4174379265, part2(&ranges));
     }
 
     #[test]
     fn test_p2_small_range() {
-        let ranges = parse_input(&"11-22,95-115");
+        let ranges = parse_input("11-22,95-115").unwrap();
         assert_eq!(243, part2(&ranges));
     }
+
+    #[test]
+    fn test_p2_str() {
+        let ranges = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(4174379265, part2_str(&ranges));
+    }
 }
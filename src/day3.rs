@@ -1,9 +1,13 @@
-use std::fs;
-use crate::timed::timed;
+use crate::parse::ParseError;
+use crate::solution::Solution;
 
-fn parse_input(input: &str) -> Vec<&str> {
+fn parse_input(input: &str) -> Result<Vec<&str>, ParseError> {
     input
         .lines()
+        .map(|l| match l.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => Err(ParseError::new(input, &l[i..], "Expected a digit")),
+            None => Ok(l),
+        })
         .collect()
 }
 
@@ -14,8 +18,9 @@ fn max_joltage(bank: &str, num_cells: usize) -> i64 {
     for i in 0..num_cells {
         let mut max_cell = 0;
         let mut cell_idx = 0;
-        for cell in start..(bank_bytes.len() - num_cells + i + 1) {
-            let cell_value = bank_bytes[cell] - '0' as u8;
+        let end = bank_bytes.len() - num_cells + i + 1;
+        for (cell, byte) in bank_bytes.iter().enumerate().take(end).skip(start) {
+            let cell_value = byte - b'0';
             if cell_value > max_cell {
                 max_cell = cell_value;
                 cell_idx = cell;
@@ -30,25 +35,31 @@ fn max_joltage(bank: &str, num_cells: usize) -> i64 {
 }
 
 pub fn part1(banks: &Vec<&str>) -> i64 {
-    banks.iter()
-        .map(|&b| max_joltage(b, 2))
-        .sum()
+    banks.iter().map(|&b| max_joltage(b, 2)).sum()
 }
 
 pub fn part2(banks: &Vec<&str>) -> i64 {
-    banks.iter()
-        .map(|&b| max_joltage(b, 12))
-        .sum()
+    banks.iter().map(|&b| max_joltage(b, 12)).sum()
 }
 
+pub struct Day3;
 
-pub fn day3() {
-    let input = fs::read_to_string("inputs/day3.txt").expect("Could not read input");
+impl Solution for Day3 {
+    type Input<'a> = Vec<&'a str>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let inputs = timed(|| parse_input(&input));
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -62,13 +73,13 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let ranges = parse_input(&TEST_INPUT);
+        let ranges = parse_input(TEST_INPUT).unwrap();
         assert_eq!(357, part1(&ranges));
     }
 
     #[test]
     fn test_p2() {
-        let ranges = parse_input(&TEST_INPUT);
+        let ranges = parse_input(TEST_INPUT).unwrap();
         assert_eq!(3121910778619, part2(&ranges));
     }
 }
@@ -1,37 +1,40 @@
-use std::cmp::PartialEq;
-use std::fs;
-use crate::timed::timed;
+use crate::parse::{ParseError, check_grid};
+use crate::solution::Solution;
 use crate::util::grid::Grid;
 use crate::util::vec2::Vec2;
+use std::cmp::PartialEq;
 
 #[derive(Clone, PartialEq)]
-enum Cell {
+pub enum Cell {
     Empty,
-    PaperRoll
+    PaperRoll,
 }
 
-fn parse_input(input: &str) -> Grid<Cell> {
-    Grid::from_str(input, |c| match c {
+fn parse_input(input: &str) -> Result<Grid<Cell>, ParseError> {
+    check_grid(input, input.lines(), ".@")?;
+
+    Ok(Grid::from_str(input, |c| match c {
         '@' => Cell::PaperRoll,
-        _ => Cell::Empty
-    })
+        _ => Cell::Empty,
+    }))
 }
 
-
 pub fn get_accessible(grid: &Grid<Cell>) -> Vec<Vec2<i64>> {
     grid.iter()
-        .filter(|(pos, value)|
-            **value == Cell::PaperRoll &&
-            grid.neighbours(pos.x, pos.y)
-                .filter(|(_, v)| *v == Cell::PaperRoll)
-                .count() < 4
-        )
+        .filter(|(pos, value)| {
+            **value == Cell::PaperRoll
+                && grid
+                    .neighbours(pos.x, pos.y)
+                    .filter(|(_, v)| *v == Cell::PaperRoll)
+                    .count()
+                    < 4
+        })
         .map(|(pos, _)| pos)
         .collect()
 }
 
 pub fn part1(grid: &Grid<Cell>) -> i64 {
-    get_accessible(&grid).len() as i64
+    get_accessible(grid).len() as i64
 }
 
 // 9ms 616us 94ns
@@ -39,29 +42,39 @@ pub fn part2(grid: &Grid<Cell>) -> i64 {
     let grid = &mut grid.clone();
     let mut num_removed = 0;
     loop {
-        let accessible = get_accessible(&grid);
-        
+        let accessible = get_accessible(grid);
+
         if accessible.is_empty() {
-            break
+            break;
         }
-        
+
         for a in accessible {
             grid.update(a.x, a.y, Cell::Empty);
             num_removed += 1;
         }
     }
-    
+
     num_removed
 }
 
+pub struct Day4;
 
-pub fn day4() {
-    let input = fs::read_to_string("inputs/day4.txt").expect("Could not read input");
+impl Solution for Day4 {
+    type Input<'a> = Grid<Cell>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let inputs = parse_input(&input);
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -81,13 +94,13 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let grid = parse_input(&TEST_INPUT);
+        let grid = parse_input(TEST_INPUT).unwrap();
         assert_eq!(13, part1(&grid));
     }
 
     #[test]
     fn test_p2() {
-        let grid = parse_input(&TEST_INPUT);
+        let grid = parse_input(TEST_INPUT).unwrap();
         assert_eq!(43, part2(&grid));
     }
 }
@@ -1,28 +1,37 @@
-use crate::timed::timed;
+use crate::parse::{ParseError, parse_number};
+use crate::solution::Solution;
 use crate::util::search::{binary_search_leftmost, binary_search_rightmost};
-use std::fs;
 
 type Range = (i64, i64);
-struct Inventory {
+pub struct Inventory {
     fresh: Vec<Range>,
     ingredients: Vec<i64>,
 }
 
-fn parse_input(input: &str) -> Inventory {
-    let (range_input, ingredient_input) = input.split_once("\n\n").expect("Could not parse input");
+fn parse_input(input: &str) -> Result<Inventory, ParseError> {
+    let (range_input, ingredient_input) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::end(
+            input,
+            "Expected a blank line between the ranges and the ingredients",
+        )
+    })?;
 
     let fresh = range_input
         .lines()
-        .filter_map(|l| l.split_once("-"))
-        .map(|(min, max)| (min.parse().unwrap(), max.parse().unwrap()))
-        .collect();
+        .map(|l| {
+            let (min, max) = l
+                .split_once("-")
+                .ok_or_else(|| ParseError::new(input, l, "Expected a range like `3-5`"))?;
+            Ok((parse_number(input, min)?, parse_number(input, max)?))
+        })
+        .collect::<Result<_, _>>()?;
 
     let ingredients = ingredient_input
         .lines()
-        .map(|l| l.parse().unwrap())
-        .collect();
+        .map(|l| parse_number(input, l))
+        .collect::<Result<_, _>>()?;
 
-    Inventory { fresh, ingredients }
+    Ok(Inventory { fresh, ingredients })
 }
 
 pub fn part1(inventory: &Inventory) -> i64 {
@@ -35,8 +44,8 @@ pub fn part1(inventory: &Inventory) -> i64 {
         let start_idx = binary_search_leftmost(&ingredients, *min);
         let end_idx = binary_search_rightmost(&ingredients, *max);
 
-        for i in start_idx..=end_idx {
-            num_range_cover[i] += 1;
+        for count in &mut num_range_cover[start_idx..=end_idx] {
+            *count += 1;
         }
     }
 
@@ -81,13 +90,24 @@ pub fn part2(inventory: &Inventory) -> i64 {
     num_fresh_ingredients
 }
 
-pub fn day5() {
-    let input = fs::read_to_string("inputs/day5.txt").expect("Could not read input");
+pub struct Day5;
 
-    let inputs = timed(|| parse_input(&input));
+impl Solution for Day5 {
+    type Input<'a> = Inventory;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -113,19 +133,19 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(3, part1(&input));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(14, part2(&input));
     }
 
     #[test]
     fn test_p2_2() {
-        let input = parse_input(&TEST_INPUT2);
+        let input = parse_input(TEST_INPUT2).unwrap();
         assert_eq!(8, part2(&input));
     }
 }
@@ -1,6 +1,6 @@
-use crate::timed::timed;
+use crate::parse::ParseError;
+use crate::solution::Solution;
 use crate::util::str_util::transpose;
-use std::fs;
 
 #[derive(PartialOrd, PartialEq, Debug)]
 enum Operator {
@@ -9,17 +9,32 @@ enum Operator {
 }
 
 #[derive(Debug)]
-struct MathProblems<'a> {
+pub struct MathProblems<'a> {
     rows: Vec<Vec<&'a str>>,
     operators: Vec<Operator>,
 }
 
+/// Lines shorter than `idx` count as space there.
 fn all_space(lines: &Vec<&str>, idx: usize) -> bool {
-    lines.iter().all(|l| l.as_bytes()[idx] == ' ' as u8)
+    lines
+        .iter()
+        .all(|l| l.as_bytes().get(idx).is_none_or(|&b| b == b' '))
 }
 
-fn parse_input(input: &str) -> MathProblems {
+/// The columns `from..to` of `line`, cut short if the line is. Problems are
+/// split at spaces, so this never cuts a character in two.
+fn columns(line: &str, from: usize, to: usize) -> &str {
+    &line[from.min(line.len())..to.min(line.len())]
+}
+
+fn parse_input(input: &str) -> Result<MathProblems<'_>, ParseError> {
     let lines: Vec<_> = input.split("\n").collect();
+    if lines.len() < 2 {
+        return Err(ParseError::end(
+            input,
+            "Expected rows of numbers followed by a row of operators",
+        ));
+    }
 
     let mut prev_end = 0;
 
@@ -40,24 +55,27 @@ fn parse_input(input: &str) -> MathProblems {
             res.rows.push(
                 lines[0..num_lines - 1]
                     .iter()
-                    .map(|&l| &l[prev_end..i])
+                    .map(|&l| columns(l, prev_end, i))
                     .collect(),
             );
 
             let op_line = lines[num_lines - 1];
 
-            let operator_str = op_line[prev_end..i.min(op_line.len())].trim();
+            let operator_str = columns(op_line, prev_end, i).trim();
             let operator = match operator_str {
                 "+" => Operator::Add,
                 "*" => Operator::Multiply,
-                _ => panic!("Unsupported operator {operator_str}"),
+                _ => {
+                    let message = format!("Unsupported operator `{operator_str}`");
+                    return Err(ParseError::new(input, operator_str, message));
+                }
             };
             res.operators.push(operator);
             prev_end = i + 1;
         }
     }
 
-    res
+    Ok(res)
 }
 
 fn do_calculation(nums: &[&str], operator: &Operator) -> i64 {
@@ -87,23 +105,31 @@ pub fn part2(input: &MathProblems) -> i64 {
     let operators = &input.operators;
     for (nums, operator) in rows.iter().zip(operators) {
         let transposed = transpose(nums);
-        let real_nums: Vec<_> = transposed
-            .iter()
-            .map(|n| n.as_str())
-            .collect();
+        let real_nums: Vec<_> = transposed.iter().map(|n| n.as_str()).collect();
         sum += do_calculation(&real_nums, operator);
     }
 
     sum
 }
 
-pub fn day6() {
-    let input = fs::read_to_string("inputs/day6.txt").expect("Could not read input");
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = MathProblems<'a>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    let inputs = timed(|| parse_input(&input));
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -117,13 +143,26 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(4277556, part1(&input));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(3263827, part2(&input));
     }
+
+    #[test]
+    fn test_parse_error() {
+        // Lines of different lengths are padded with spaces
+        let input = parse_input("12 3\n4\n+  *").unwrap();
+        assert_eq!(vec![vec!["12", "4"], vec!["3", ""]], input.rows);
+
+        let error = parse_input("\n  ").unwrap_err();
+        assert_eq!("Unsupported operator ``", error.message);
+
+        let error = parse_input("1 é2\n+ -").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
 }
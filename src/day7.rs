@@ -1,48 +1,51 @@
-use crate::timed::timed;
-use crate::util::str_util::transpose;
-use std::fs;
+use crate::parse::{ParseError, check_grid};
+use crate::solution::Solution;
+use crate::util::grid::GridChar;
 use std::mem::swap;
 
 #[derive(PartialOrd, PartialEq, Debug, Clone, Copy)]
-enum Cell {
+pub enum Cell {
     Empty,
     Start,
     Beam,
     Splitter,
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Cell>> {
-    input
+impl GridChar for Cell {
+    fn to_char(&self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Beam => '|',
+            Cell::Splitter => '^',
+            Cell::Start => 'S',
+        }
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Cell::Empty),
+            '|' => Some(Cell::Beam),
+            '^' => Some(Cell::Splitter),
+            'S' => Some(Cell::Start),
+            _ => None,
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<Cell>>, ParseError> {
+    check_grid(input, input.lines(), ".S^")?;
+
+    Ok(input
         .lines()
         .map(|l| {
             l.chars()
-                .map(|c| match c {
-                    '.' => Cell::Empty,
-                    'S' => Cell::Start,
-                    '^' => Cell::Splitter,
-                    o => panic!("Unknown cell {o}"),
-                })
+                .map(|c| Cell::from_char(c).expect("Checked by check_grid"))
                 .collect()
         })
-        .collect()
-}
-
-fn print_line(line: &Vec<Cell>) {
-    println!(
-        "{}",
-        line.iter()
-            .map(|c| match c {
-                Cell::Empty => ".",
-                Cell::Beam => "|",
-                Cell::Splitter => "^",
-                Cell::Start => "S",
-            })
-            .collect::<Vec<_>>()
-            .join("")
-    );
+        .collect())
 }
 
-pub fn part1(input: &Vec<Vec<Cell>>) -> i64 {
+pub fn part1(input: &[Vec<Cell>]) -> i64 {
     let mut current_line: Vec<_> = input[0]
         .iter()
         .map(|c| match c {
@@ -67,18 +70,15 @@ pub fn part1(input: &Vec<Vec<Cell>>) -> i64 {
 
         swap(&mut current_line, &mut next_line);
 
-        for i in 0..next_line.len() {
-            next_line[i] = Cell::Empty;
-        }
+        next_line.fill(Cell::Empty);
     }
 
     num_splits
 }
 
-pub fn part2(input: &Vec<Vec<Cell>>) -> i64 {
+pub fn part2(input: &[Vec<Cell>]) -> i64 {
     let mut prev_num_paths = vec![1; input[0].len()];
     let mut current_num_paths = vec![1; input[0].len()];
-    
 
     for line in input.iter().rev().skip(1) {
         for i in 0..line.len() {
@@ -86,7 +86,7 @@ pub fn part2(input: &Vec<Vec<Cell>>) -> i64 {
             if cell == Cell::Start {
                 return prev_num_paths[i];
             }
-            
+
             current_num_paths[i] = match cell {
                 Cell::Splitter => prev_num_paths[i - 1] + prev_num_paths[i + 1],
                 _ => prev_num_paths[i],
@@ -97,13 +97,24 @@ pub fn part2(input: &Vec<Vec<Cell>>) -> i64 {
     panic!("No start?")
 }
 
-pub fn day7() {
-    let input = fs::read_to_string("inputs/day7.txt").expect("Could not read input");
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Vec<Vec<Cell>>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    let inputs = timed(|| parse_input(&input));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -129,13 +140,19 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(21, part1(&input));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(40, part2(&input));
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_input("..S..\n..x..").unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+    }
 }
@@ -1,34 +1,37 @@
-use crate::timed::{print_timespan, timed};
-use crate::util::str_util::transpose;
+use crate::parse::{ParseError, parse_number};
+use crate::solution::Solution;
+use crate::timed::{in_span, span};
+use crate::util::simd::{Simd, i64x8};
 use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::fs;
-use std::mem::swap;
-use std::ops::{Add, Mul, Sub};
-use std::rc::Rc;
-use std::simd::{Simd, i64x8, u32x8};
-use std::time::Instant;
+use std::collections::{BinaryHeap, HashSet};
 
 #[derive(Copy, Clone)]
-struct Node {
+pub struct Node {
     id: usize,
     x: i64,
     y: i64,
     z: i64,
 }
 
-fn parse_input(input: &str) -> Vec<Node> {
+fn parse_input(input: &str) -> Result<Vec<Node>, ParseError> {
     input
         .lines()
         .enumerate()
         .map(|(id, l)| {
-            let mut parts = l.splitn(3, ",").map(|p| p.parse::<i64>().unwrap());
-            Node {
+            let mut parts = l.splitn(3, ",");
+            let (Some(x), Some(y), Some(z)) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(ParseError::new(
+                    input,
+                    l,
+                    "Expected coordinates like `162,817,812`",
+                ));
+            };
+            Ok(Node {
                 id,
-                x: parts.next().unwrap(),
-                y: parts.next().unwrap(),
-                z: parts.next().unwrap(),
-            }
+                x: parse_number(input, x)?,
+                y: parse_number(input, y)?,
+                z: parse_number(input, z)?,
+            })
         })
         .collect()
 }
@@ -80,7 +83,7 @@ fn find_pairs(input: &Vec<Node>, num_pairs: usize) -> Vec<NodePair> {
     }
     let num_inputs = input.len();
 
-    for i in 0..input.len() % 8 + 8 {
+    for _i in 0..input.len() % 8 + 8 {
         xs.push(0);
         ys.push(0);
         zs.push(0);
@@ -127,19 +130,17 @@ fn find_pairs(input: &Vec<Node>, num_pairs: usize) -> Vec<NodePair> {
 }
 
 pub fn part1(input: &Vec<Node>, num_pairs: usize) -> i64 {
-    let start = Instant::now();
-
-    let mut closest_pairs = find_pairs(input, num_pairs);
+    let closest_pairs = in_span("Find pairs", || find_pairs(input, num_pairs));
 
-    let found_pairs = Instant::now();
+    let form_circuits = span("Form circuits");
 
-    let mut circuit_ids: Vec<_> = (0..input.len()).map(|c| None).collect();
+    let mut circuit_ids: Vec<_> = (0..input.len()).map(|_c| None).collect();
     let mut circuits: Vec<HashSet<usize>> = Vec::new();
 
     for pair in closest_pairs.iter().take(num_pairs) {
         let cid0 = circuit_ids[pair.id0];
         let cid1 = circuit_ids[pair.id1];
-        if cid0 != None && cid0 == cid1 {
+        if cid0.is_some() && cid0 == cid1 {
             continue;
         }
 
@@ -169,33 +170,29 @@ pub fn part1(input: &Vec<Node>, num_pairs: usize) -> i64 {
         }
     }
 
-    let form_circuits = Instant::now();
-
-    circuits.sort_by_key(|circuit| Reverse(circuit.len()));
-
-    let sorted_circuits = Instant::now();
+    drop(form_circuits);
 
-    print_timespan("Find pairs", found_pairs - start);
-    print_timespan("Form circuits", form_circuits - found_pairs);
-    print_timespan("Sort circuits", sorted_circuits - form_circuits);
+    in_span("Sort circuits", || {
+        circuits.sort_by_key(|circuit| Reverse(circuit.len()))
+    });
 
     (circuits[0].len() * circuits[1].len() * circuits[2].len()) as i64
 }
 
 pub fn part2(input: &Vec<Node>) -> i64 {
-    let start = Instant::now();
+    let closest_pairs = in_span("Find pairs", || {
+        find_pairs(input, input.len() * input.len())
+    });
 
-    let mut closest_pairs = find_pairs(input, input.len() * input.len());
+    let _form_circuits = span("Form circuits");
 
-    let found_pairs = Instant::now();
-
-    let mut circuit_ids: Vec<_> = (0..input.len()).map(|c| None).collect();
+    let mut circuit_ids: Vec<_> = (0..input.len()).map(|_c| None).collect();
     let mut circuits: Vec<HashSet<usize>> = Vec::new();
 
     for pair in closest_pairs.iter() {
         let cid0 = circuit_ids[pair.id0];
         let cid1 = circuit_ids[pair.id1];
-        if cid0 != None && cid0 == cid1 {
+        if cid0.is_some() && cid0 == cid1 {
             continue;
         }
 
@@ -225,9 +222,6 @@ pub fn part2(input: &Vec<Node>) -> i64 {
         }
 
         if circuits[new_circuit].len() == input.len() {
-            let form_circuits = Instant::now();
-            print_timespan("Find pairs", found_pairs - start);
-            print_timespan("Form circuits", form_circuits - found_pairs);
             return input[pair.id0].x * input[pair.id1].x;
         }
     }
@@ -235,13 +229,32 @@ pub fn part2(input: &Vec<Node>) -> i64 {
     panic!("wtf");
 }
 
-pub fn day8() {
-    let input = fs::read_to_string("inputs/day8.txt").expect("Could not read input");
+pub struct Day8 {
+    pub num_pairs: usize,
+}
+
+impl Default for Day8 {
+    fn default() -> Self {
+        Self { num_pairs: 1000 }
+    }
+}
 
-    let inputs = timed(|| parse_input(&input));
+impl Solution for Day8 {
+    type Input<'a> = Vec<Node>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
 
-    println!("Part 1: {}", timed(|| part1(&inputs, 1000)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input, self.num_pairs)
+    }
+
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -271,13 +284,13 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(40, part1(&input, 10));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(25272, part2(&input));
     }
 }
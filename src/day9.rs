@@ -1,36 +1,32 @@
-use crate::timed::{print_timespan, timed};
+use crate::parse::{ParseError, parse_number};
+use crate::solution::Solution;
+use crate::timed::span;
+use crate::util::simd::{Simd, SimdInt, SimdOrd, i64x8};
 use crate::util::spatial_grid::SpatialGrid;
-use crate::util::str_util::transpose;
 use crate::util::vec2::Vec2;
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::fs;
-use std::mem::swap;
-use std::net::AddrParseError;
-use std::rc::Rc;
-use std::simd::cmp::SimdOrd;
-use std::simd::num::SimdInt;
-use std::simd::{Simd, i64x8, u32x8};
-use std::time::Instant;
+use std::cmp::Reverse;
 
 #[derive(Copy, Clone)]
-struct Node {
+pub struct Node {
     id: usize,
     x: i64,
     y: i64,
 }
 
-fn parse_input(input: &str) -> Vec<Node> {
+fn parse_input(input: &str) -> Result<Vec<Node>, ParseError> {
     input
         .lines()
         .enumerate()
         .map(|(id, l)| {
-            let mut parts = l.splitn(2, ",").map(|p| p.parse::<i64>().unwrap());
-            Node {
+            let mut parts = l.splitn(2, ",");
+            let (Some(x), Some(y)) = (parts.next(), parts.next()) else {
+                return Err(ParseError::new(input, l, "Expected coordinates like `7,1`"));
+            };
+            Ok(Node {
                 id,
-                x: parts.next().unwrap(),
-                y: parts.next().unwrap(),
-            }
+                x: parse_number(input, x)?,
+                y: parse_number(input, y)?,
+            })
         })
         .collect()
 }
@@ -51,7 +47,7 @@ pub fn part1(input: &Vec<Node>) -> i64 {
         ys.push(i.y);
     }
 
-    for i in 0..input.len() % 8 + 8 {
+    for _i in 0..input.len() % 8 + 8 {
         xs.push(0);
         ys.push(0);
     }
@@ -66,7 +62,7 @@ pub fn part1(input: &Vec<Node>) -> i64 {
         })
         .collect();
 
-    for i in (0..input.len() - 1).step_by(8) {
+    for i in (0..input.len().saturating_sub(1)).step_by(8) {
         let n1x = Simd::from_slice(&xs[i..i + 8]);
         let n1y = Simd::from_slice(&ys[i..i + 8]);
 
@@ -78,7 +74,7 @@ pub fn part1(input: &Vec<Node>) -> i64 {
 
             if j + 8 >= num_inputs {
                 let area_mask = area_masks[j + 8 - num_inputs];
-                areas = areas * area_mask;
+                areas *= area_mask;
             }
 
             max_area = max_area.simd_max(areas);
@@ -88,12 +84,12 @@ pub fn part1(input: &Vec<Node>) -> i64 {
     max_area.reduce_max()
 }
 
-fn shrink_outline(xs: &Vec<i64>, ys: &Vec<i64>, len: usize) -> (Vec<i64>, Vec<i64>) {
+pub fn shrink_outline(xs: &[i64], ys: &[i64], len: usize) -> (Vec<i64>, Vec<i64>) {
     let mut x_mod = 0;
     let mut y_mod = 0;
 
-    let mut xs = xs.clone();
-    let mut ys = ys.clone();
+    let mut xs = xs.to_vec();
+    let mut ys = ys.to_vec();
 
     for i in 0..len {
         let j = (i + 1) % len;
@@ -117,7 +113,7 @@ fn shrink_outline(xs: &Vec<i64>, ys: &Vec<i64>, len: usize) -> (Vec<i64>, Vec<i6
     (xs, ys)
 }
 
-fn surrounded_area(len: usize, xs: &Vec<i64>, ys: &Vec<i64>, from: usize, to: usize) -> i64 {
+pub fn surrounded_area(len: usize, xs: &[i64], ys: &[i64], from: usize, to: usize) -> i64 {
     let min_x = xs[from].min(xs[to]);
     let max_x = xs[from].max(xs[to]);
     let min_y = ys[from].min(ys[to]);
@@ -137,8 +133,71 @@ fn surrounded_area(len: usize, xs: &Vec<i64>, ys: &Vec<i64>, from: usize, to: us
     area.abs()
 }
 
+/// Whether the point at half the coordinates `x2` and `y2` is inside the
+/// outline or on it.
+fn contains_doubled(xs: &[i64], ys: &[i64], x2: i64, y2: i64) -> bool {
+    let len = xs.len();
+    let mut crossings = 0;
+    for r in 0..len {
+        let nr = (r + 1) % len;
+        let (min_x, max_x) = (2 * xs[r].min(xs[nr]), 2 * xs[r].max(xs[nr]));
+        let (min_y, max_y) = (2 * ys[r].min(ys[nr]), 2 * ys[r].max(ys[nr]));
+        if (min_x..=max_x).contains(&x2) && (min_y..=max_y).contains(&y2) {
+            return true;
+        }
+        // Count the vertical edges to the right, half open so that a ray
+        // through a corner counts once
+        if min_x == max_x && min_x > x2 && (min_y..max_y).contains(&y2) {
+            crossings += 1;
+        }
+    }
+    crossings % 2 == 1
+}
+
+/// Whether every tile of the rectangle from `min` to `max` is red or green.
+fn rectangle_inside(xs: &[i64], ys: &[i64], min: Vec2<i64>, max: Vec2<i64>) -> bool {
+    let len = xs.len();
+    let edges = (0..len).map(|r| {
+        let nr = (r + 1) % len;
+        (
+            Vec2::new(xs[r].min(xs[nr]), ys[r].min(ys[nr])),
+            Vec2::new(xs[r].max(xs[nr]), ys[r].max(ys[nr])),
+        )
+    });
+
+    if min.x < max.x && min.y < max.y {
+        // Without an edge through it the inside of the rectangle is either
+        // all in or all out of the outline
+        let crossed = edges
+            .clone()
+            .any(|(lo, hi)| lo.x < max.x && hi.x > min.x && lo.y < max.y && hi.y > min.y);
+        return !crossed && contains_doubled(xs, ys, min.x + max.x, min.y + max.y);
+    }
+
+    // A single row or column: check between every place an edge touches it
+    let vertical = min.x == max.x;
+    let along = |v: Vec2<i64>| if vertical { v.y } else { v.x };
+    let mut stops = vec![along(min), along(max)];
+    for (lo, hi) in edges {
+        if lo.x <= max.x && hi.x >= min.x && lo.y <= max.y && hi.y >= min.y {
+            stops.push(along(lo).max(along(min)));
+            stops.push(along(hi).min(along(max)));
+        }
+    }
+    stops.sort_unstable();
+    stops.dedup();
+    stops.windows(2).all(|w| {
+        let middle = w[0] + w[1];
+        if vertical {
+            contains_doubled(xs, ys, 2 * min.x, middle)
+        } else {
+            contains_doubled(xs, ys, middle, 2 * min.y)
+        }
+    })
+}
+
 pub fn part2(input: &Vec<Node>) -> i64 {
-    let start = Instant::now();
+    let build_grid = span("Build grid");
 
     let mut xs = Vec::new();
     let mut ys = Vec::new();
@@ -162,7 +221,7 @@ pub fn part2(input: &Vec<Node>) -> i64 {
         ys.push(i.y);
     }
 
-    for i in 0..input.len() % 8 + 8 {
+    for _i in 0..input.len() % 8 + 8 {
         xs.push(0);
         ys.push(0);
     }
@@ -177,7 +236,10 @@ pub fn part2(input: &Vec<Node>) -> i64 {
     let mut max_area = 0;
     let mut area_buffer = Vec::new();
 
-    for i in (0..input.len() - 1).step_by(8) {
+    drop(build_grid);
+    let _find_area = span("Find largest area");
+
+    for i in (0..input.len().saturating_sub(1)).step_by(8) {
         let n1x = Simd::from_slice(&xs[i..i + 8]);
         let n1y = Simd::from_slice(&ys[i..i + 8]);
 
@@ -189,51 +251,29 @@ pub fn part2(input: &Vec<Node>) -> i64 {
 
             if j + 8 > num_inputs {
                 let area_mask = area_masks[j + 8 - num_inputs];
-                areas = areas * area_mask;
+                areas *= area_mask;
             }
 
             let area = areas.to_array();
-            for k in 0..area.len() {
-                let a = area[k];
+            for (k, &a) in area.iter().enumerate() {
                 if a > max_area {
                     area_buffer.push((i + k, j + k, a));
                 }
             }
         }
-        if area_buffer.len() > 100 || i >= input.len() - 8 {
+        if area_buffer.len() > 100 || i + 8 >= input.len() {
+            let _check = span("Check candidates");
             area_buffer.sort_by_key(|&(_, _, area)| Reverse(area));
-            'area_loop: for &(i, j, area) in &area_buffer {
+            for &(i, j, area) in &area_buffer {
                 if area <= max_area {
                     break;
                 }
 
                 let min = Vec2::new(xs[i].min(xs[j]), ys[i].min(ys[j]));
                 let max = Vec2::new(xs[i].max(xs[j]), ys[i].max(ys[j]));
-
-                for r in 0..num_inputs {
-                    let v = Vec2::new(xs[r], ys[r]);
-
-                    let nr = (r + 1) % num_inputs;
-                    let nv = Vec2::new(xs[nr], ys[nr]);
-
-                    if v.in_bounds(min + Vec2::one(), max - Vec2::one()) {
-                        continue 'area_loop;
-                    }
-
-                    let inside_y =
-                        (v.y >= min.y && v.y <= max.y) || (nv.y >= min.y && nv.y <= max.y);
-                    let inside_x =
-                        (v.x >= min.x && v.x <= max.x) || (nv.x >= min.x && nv.x <= max.x);
-
-                    if (v.x > max.x && nv.x < max.x && inside_y)
-                        || (v.x > min.x && nv.x < min.x && inside_y)
-                        || (v.y > max.y && nv.y < max.y && inside_x)
-                        || (v.y > min.y && nv.y < min.y && inside_x)
-                    {
-                        continue 'area_loop;
-                    }
+                if rectangle_inside(&xs[..num_inputs], &ys[..num_inputs], min, max) {
+                    max_area = area;
                 }
-                max_area = area;
             }
             area_buffer.clear();
         }
@@ -242,25 +282,32 @@ pub fn part2(input: &Vec<Node>) -> i64 {
     max_area
 }
 
-pub fn day9() {
-    let input = fs::read_to_string("inputs/day9.txt").expect("Could not read input");
+pub struct Day9;
 
-    let inputs = timed(|| parse_input(&input));
+impl Solution for Day9 {
+    type Input<'a> = Vec<Node>;
+    type Part1 = i64;
+    type Part2 = i64;
 
-    println!("Part 1: {}", timed(|| part1(&inputs)));
-    println!("Part 2: {}", timed(|| part2(&inputs)));
-}
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-// 222529760 too low
-// 1410470448 too low
-// 1410501884
-// 1478326656 wrong
-// 2859243744 too high
-//
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::Rng;
+    use crate::util::property::{Case, differential};
+    use std::collections::{BTreeMap, BTreeSet};
 
     const TEST_INPUT: &str = "7,1
 11,1
@@ -273,13 +320,203 @@ mod tests {
 
     #[test]
     fn test_p1() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(50, part1(&input));
     }
 
     #[test]
     fn test_p2() {
-        let input = parse_input(&TEST_INPUT);
+        let input = parse_input(TEST_INPUT).unwrap();
         assert_eq!(24, part2(&input));
     }
+
+    #[test]
+    fn test_p2_cut_by_outline() {
+        // Red tiles 11,13 and 17,11 span 21 tiles, but the ones up and to
+        // the left of 13,13 are outside the outline
+        let input = parse_input("11,13\n13,13\n13,11\n17,11\n17,13\n15,13\n15,15\n11,15").unwrap();
+        assert_eq!(15, part2(&input));
+    }
+
+    /// The union of unit cells whose outline is the loop of red tiles
+    #[derive(Clone, Debug)]
+    struct Shape(BTreeSet<(i64, i64)>);
+
+    impl Shape {
+        fn random(rng: &mut Rng) -> Self {
+            loop {
+                let size = rng.range(1, 25) as usize;
+                let mut cells = BTreeSet::from([(rng.range(0, 7), rng.range(0, 7))]);
+                while cells.len() < size {
+                    let &(x, y) = cells.iter().nth(rng.index(cells.len())).unwrap();
+                    let (dx, dy) = [(1, 0), (-1, 0), (0, 1), (0, -1)][rng.index(4)];
+                    if (0..8).contains(&(x + dx)) && (0..8).contains(&(y + dy)) {
+                        cells.insert((x + dx, y + dy));
+                    }
+                }
+                let shape = Shape(cells);
+                if shape.corners().is_some() {
+                    return shape;
+                }
+            }
+        }
+
+        /// Corners of the outline in order, `None` if the cells have holes or
+        /// touch only diagonally, so the outline is not a simple loop.
+        fn corners(&self) -> Option<Vec<(i64, i64)>> {
+            let cells = &self.0;
+            let &start = cells.first()?;
+
+            // Connected, and without holes: the outside is connected as well
+            let min = cells
+                .iter()
+                .fold((i64::MAX, i64::MAX), |m, c| (m.0.min(c.0), m.1.min(c.1)));
+            let max = cells
+                .iter()
+                .fold((i64::MIN, i64::MIN), |m, c| (m.0.max(c.0), m.1.max(c.1)));
+            let in_bounds = |(x, y): (i64, i64)| {
+                (min.0 - 1..=max.0 + 1).contains(&x) && (min.1 - 1..=max.1 + 1).contains(&y)
+            };
+            let reachable = |from: (i64, i64), inside: bool| {
+                let mut seen = BTreeSet::from([from]);
+                let mut todo = vec![from];
+                while let Some((x, y)) = todo.pop() {
+                    for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                        if in_bounds(next) && cells.contains(&next) == inside && seen.insert(next) {
+                            todo.push(next);
+                        }
+                    }
+                }
+                seen.len()
+            };
+            let area = ((max.0 - min.0 + 3) * (max.1 - min.1 + 3)) as usize;
+            if reachable(start, true) != cells.len()
+                || reachable((min.0 - 1, min.1 - 1), false) != area - cells.len()
+            {
+                return None;
+            }
+            for x in min.0 - 1..=max.0 {
+                for y in min.1 - 1..=max.1 {
+                    let a = cells.contains(&(x, y));
+                    let b = cells.contains(&(x + 1, y));
+                    let c = cells.contains(&(x, y + 1));
+                    let d = cells.contains(&(x + 1, y + 1));
+                    if a == d && b == c && a != b {
+                        return None;
+                    }
+                }
+            }
+
+            // Walk the outline clockwise, keeping the points where it turns
+            let mut next = BTreeMap::new();
+            for &(x, y) in cells {
+                let sides = [
+                    ((x, y - 1), (x, y), (x + 1, y)),
+                    ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+                    ((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+                    ((x - 1, y), (x, y + 1), (x, y)),
+                ];
+                for (neighbour, from, to) in sides {
+                    if !cells.contains(&neighbour) {
+                        next.insert(from, to);
+                    }
+                }
+            }
+            let mut outline = vec![start];
+            while outline.len() < next.len() {
+                outline.push(next[outline.last().unwrap()]);
+            }
+            let len = outline.len();
+            let corners = (0..len)
+                .filter(|&i| {
+                    let (p, c, n) = (
+                        outline[(i + len - 1) % len],
+                        outline[i],
+                        outline[(i + 1) % len],
+                    );
+                    (c.0 - p.0, c.1 - p.1) != (n.0 - c.0, n.1 - c.1)
+                })
+                .map(|i| (outline[i].0 * 2 + 1, outline[i].1 * 2 + 1))
+                .collect();
+            Some(corners)
+        }
+    }
+
+    impl Case for Shape {
+        fn input(&self) -> String {
+            let corners: Vec<_> = self
+                .corners()
+                .unwrap()
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect();
+            corners.join("\n")
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            self.0
+                .iter()
+                .map(|cell| {
+                    let mut cells = self.0.clone();
+                    cells.remove(cell);
+                    Shape(cells)
+                })
+                .filter(|shape| shape.corners().is_some())
+                .collect()
+        }
+    }
+
+    /// Checks every tile of every rectangle.
+    fn part2_naive(input: &Vec<Node>) -> i64 {
+        let len = input.len();
+        let on_outline = |x: i64, y: i64| {
+            (0..len).any(|i| {
+                let (a, b) = (input[i], input[(i + 1) % len]);
+                (a.x.min(b.x)..=a.x.max(b.x)).contains(&x)
+                    && (a.y.min(b.y)..=a.y.max(b.y)).contains(&y)
+            })
+        };
+        let inside = |x: i64, y: i64| {
+            let crossings = (0..len)
+                .filter(|&i| {
+                    let (a, b) = (input[i], input[(i + 1) % len]);
+                    a.x == b.x && a.x > x && (a.y.min(b.y)..a.y.max(b.y)).contains(&y)
+                })
+                .count();
+            crossings % 2 == 1
+        };
+
+        let mut max_area = 0;
+        for a in input {
+            for b in input {
+                let xs = a.x.min(b.x)..=a.x.max(b.x);
+                let ys = a.y.min(b.y)..=a.y.max(b.y);
+                let area = (xs.end() - xs.start() + 1) * (ys.end() - ys.start() + 1);
+                if area > max_area
+                    && xs
+                        .clone()
+                        .all(|x| ys.clone().all(|y| on_outline(x, y) || inside(x, y)))
+                {
+                    max_area = area;
+                }
+            }
+        }
+        max_area
+    }
+
+    #[test]
+    fn test_p2_naive() {
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(24, part2_naive(&input));
+    }
+
+    #[test]
+    fn test_p2_matches_naive() {
+        differential(
+            200,
+            Shape::random,
+            |input| part2(&parse_input(input).unwrap()),
+            |input| part2_naive(&parse_input(input).unwrap()),
+        );
+    }
 }
@@ -0,0 +1,346 @@
+//! Examples and their answers, extracted from saved puzzle descriptions.
+//!
+//! Save the puzzle page as `puzzles/day5.html` (or the markdown version as
+//! `day5.md`). Example inputs are the `<pre><code>` blocks (fenced code blocks
+//! in markdown), the answer of a part is the last emphasised code in its
+//! section, e.g. `<code><em>50</em></code>` or `` `*50*` ``. Everything works
+//! on the local file, nothing is downloaded.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::solution::Part;
+
+pub const PUZZLE_DIR: &str = "puzzles";
+
+const PART_TWO_MARKER: &str = "--- Part Two ---";
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Example {
+    /// Part in whose description the example appears
+    pub part: Part,
+    pub input: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Puzzle {
+    pub examples: Vec<Example>,
+    pub answers: Vec<(Part, String)>,
+}
+
+impl Puzzle {
+    /// Parses an HTML or markdown puzzle description.
+    pub fn extract(text: &str) -> Self {
+        let (part1, part2) = match text.find(PART_TWO_MARKER) {
+            Some(i) => text.split_at(i),
+            None => (text, ""),
+        };
+        let is_html = text.contains("<pre>") || text.contains("<article");
+
+        let mut puzzle = Puzzle::default();
+        for (part, section) in [(Part::One, part1), (Part::Two, part2)] {
+            let (blocks, answer) = if is_html {
+                (html_blocks(section), html_answer(section))
+            } else {
+                (markdown_blocks(section), markdown_answer(section))
+            };
+
+            puzzle
+                .examples
+                .extend(blocks.into_iter().map(|input| Example { part, input }));
+            if let Some(answer) = answer {
+                puzzle.answers.push((part, answer));
+            }
+        }
+        puzzle
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        Ok(Self::extract(&text))
+    }
+
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, a)| a.as_str())
+    }
+
+    /// The example the answer of `part` belongs to: the first one given in
+    /// that part, or the first one of part one if part two has none.
+    pub fn example(&self, part: Part) -> Option<&str> {
+        self.examples
+            .iter()
+            .find(|e| e.part == part)
+            .or_else(|| self.examples.first())
+            .map(|e| e.input.as_str())
+    }
+
+    /// A `#[cfg(test)]` module for the day, in the style of the existing ones.
+    /// With an `ignore` reason the tests are ignored, for a day that is not
+    /// solved yet.
+    pub fn test_module(&self, ignore: Option<&str>) -> String {
+        let mut out = String::from("#[cfg(test)]\nmod tests {\n    use super::*;\n");
+
+        let mut consts: Vec<&str> = Vec::new();
+        let mut tests = String::new();
+        for part in Part::ALL {
+            let (Some(example), Some(answer)) = (self.example(part), self.answer(part)) else {
+                continue;
+            };
+            let index = match consts.iter().position(|&c| c == example) {
+                Some(index) => index,
+                None => {
+                    consts.push(example);
+                    consts.len() - 1
+                }
+            };
+
+            let expected = if answer.parse::<i64>().is_ok() {
+                answer.to_string()
+            } else {
+                format!("{answer:?}")
+            };
+            let n = part;
+            let attribute = ignore.map_or(String::new(), |reason| {
+                format!("\n    #[ignore = {reason:?}]")
+            });
+            write!(
+                tests,
+                "\n    #[test]{attribute}\n    fn test_p{n}() {{\n        let input = parse_input({}).unwrap();\n        assert_eq!({expected}, part{n}(&input));\n    }}\n",
+                const_name(index)
+            )
+            .unwrap();
+        }
+
+        for (index, example) in consts.iter().enumerate() {
+            write!(
+                out,
+                "\n    const {}: &str = {};\n",
+                const_name(index),
+                string_literal(example)
+            )
+            .unwrap();
+        }
+        out.push_str(&tests);
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Path of the saved description of `day`, either `dayN.html` or `dayN.md`.
+pub fn find(dir: &Path, day: u32) -> Result<PathBuf, String> {
+    let candidates = [
+        dir.join(format!("day{day}.html")),
+        dir.join(format!("day{day}.md")),
+    ];
+    candidates
+        .iter()
+        .find(|p| p.is_file())
+        .cloned()
+        .ok_or(format!(
+            "No puzzle description for day {day}, save it as {} or {}",
+            candidates[0].display(),
+            candidates[1].display()
+        ))
+}
+
+fn const_name(index: usize) -> String {
+    match index {
+        0 => "TEST_INPUT".to_string(),
+        i => format!("TEST_INPUT_{}", i + 1),
+    }
+}
+
+/// A plain string literal like the hand written examples, a raw one if the
+/// example contains quotes or backslashes.
+fn string_literal(s: &str) -> String {
+    if !s.contains(['"', '\\']) {
+        return format!("\"{s}\"");
+    }
+    let mut hashes = String::from("#");
+    while s.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{s}\"{hashes}")
+}
+
+/// Contents of every `start`...`end` delimited block.
+fn between<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    while let Some(i) = rest.find(start) {
+        rest = &rest[i + start.len()..];
+        let Some(j) = rest.find(end) else {
+            break;
+        };
+        blocks.push(&rest[..j]);
+        rest = &rest[j + end.len()..];
+    }
+    blocks
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn html_blocks(html: &str) -> Vec<String> {
+    between(html, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .map(|block| strip_tags(block).trim_end_matches('\n').to_string())
+        .collect()
+}
+
+fn html_answer(html: &str) -> Option<String> {
+    between(html, "<code><em>", "</em></code>")
+        .last()
+        .map(|answer| strip_tags(answer))
+}
+
+fn markdown_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut block: Option<Vec<&str>> = None;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(lines) => blocks.push(lines.join("\n")),
+                None => block = Some(Vec::new()),
+            }
+        } else if let Some(lines) = &mut block {
+            lines.push(line);
+        }
+    }
+    blocks
+}
+
+fn markdown_answer(markdown: &str) -> Option<String> {
+    // Only look at inline code outside of the fenced blocks
+    let mut in_block = false;
+    let prose: Vec<&str> = markdown
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_block = !in_block;
+                return false;
+            }
+            !in_block
+        })
+        .collect();
+
+    let prose = prose.join("\n");
+
+    // Inline code that is emphasised, either as `*50*` or as *`50`*
+    let mut answer = None;
+    let mut rest = prose.as_str();
+    while let Some(start) = rest.find('`') {
+        let Some(len) = rest[start + 1..].find('`') else {
+            break;
+        };
+        let code = &rest[start + 1..start + 1 + len];
+        let after = &rest[start + len + 2..];
+
+        let emphasised = rest[..start].ends_with('*') && after.starts_with('*');
+        let code = code.trim_matches('*');
+        if (emphasised || code.len() + 2 <= len) && !code.is_empty() {
+            answer = Some(code.to_string());
+        }
+        rest = after;
+    }
+    answer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = "<article class=\"day-desc\"><h2>--- Day 9: Movie Theater ---</h2>
+<p>For example:</p>
+<pre><code>7,1
+11,1
+<em>2,5</em>
+</code></pre>
+<p>The <code>&lt;</code> tiles, the largest area is <code><em>50</em></code>.</p>
+</article><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>Using the same example, at most <code>10</code> tiles, so <code><em>24</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>4749929916</code>.</p>";
+
+    const MARKDOWN: &str = "## --- Day 9: Movie Theater ---
+
+For example:
+
+```
+7,1
+11,1
+```
+
+The largest area is `*50*`.
+
+## --- Part Two ---
+
+A new example:
+
+```
+a<b
+```
+
+Now the answer is *`24`*, not `25`.
+";
+
+    #[test]
+    fn test_html() {
+        let puzzle = Puzzle::extract(HTML);
+        assert_eq!(
+            vec![Example {
+                part: Part::One,
+                input: "7,1\n11,1\n2,5".to_string()
+            }],
+            puzzle.examples
+        );
+        assert_eq!(Some("50"), puzzle.answer(Part::One));
+        assert_eq!(Some("24"), puzzle.answer(Part::Two));
+        assert_eq!(Some("7,1\n11,1\n2,5"), puzzle.example(Part::Two));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let puzzle = Puzzle::extract(MARKDOWN);
+        assert_eq!(Some("7,1\n11,1"), puzzle.example(Part::One));
+        assert_eq!(Some("a<b"), puzzle.example(Part::Two));
+        assert_eq!(Some("50"), puzzle.answer(Part::One));
+        assert_eq!(Some("24"), puzzle.answer(Part::Two));
+    }
+
+    #[test]
+    fn test_test_module() {
+        let module = Puzzle::extract(MARKDOWN).test_module(None);
+        assert!(module.contains("const TEST_INPUT: &str = \"7,1\n11,1\";"));
+        assert!(module.contains("const TEST_INPUT_2: &str = \"a<b\";"));
+        assert!(module.contains("let input = parse_input(TEST_INPUT_2).unwrap();"));
+        assert!(module.contains("assert_eq!(24, part2(&input));"));
+
+        assert!(!module.contains("#[ignore"));
+        let module = Puzzle::extract(MARKDOWN).test_module(Some("not solved yet"));
+        assert!(module.contains("#[test]\n    #[ignore = \"not solved yet\"]\n    fn test_p2() {"));
+
+        assert_eq!("r#\"say \"hi\"\"#", string_literal("say \"hi\""));
+        assert_eq!("r##\"\"#\"##", string_literal("\"#"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Fuzzing of the input parsers.
+//!
+//! Inputs from [`generate`] are mutated at random and handed to the parser of
+//! the day, which has to return either the parsed input or a [`ParseError`],
+//! but never panic. Crashing inputs are minimized before they are reported.
+//! Only the parser runs, the parts are not solved.
+
+use std::fmt::{Display, Formatter};
+
+use crate::generate::{Rng, generate};
+use crate::solution::{Day, Plain};
+use crate::util::panic::catch;
+
+/// Upper bound on the characters an input is minimized to one by one
+const MAX_MINIMIZE_CHARS: usize = 500;
+
+/// Characters that mean something to at least one of the parsers
+const DICTIONARY: &[char] = &[
+    '0', '1', '5', '9', ' ', '\n', '\r', '\t', ',', '-', ':', 'x', '#', '.', '@', '(', ')', '[',
+    ']', '{', '}', '+', '*', '^', 'S', 'L', 'R', 'é',
+];
+
+/// Tokens that are likely to hit edge cases, such as overflowing numbers
+const TOKENS: &[&str] = &[
+    "99999999999999999999999",
+    "-1",
+    "0x0",
+    ": ",
+    "\n\n",
+    "svr: ",
+    " out",
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crash {
+    pub day: u32,
+    /// Minimized input the parser panicked on
+    pub input: String,
+    pub message: String,
+}
+
+impl Display for Crash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {} parser {} on input {:?}",
+            self.day, self.message, self.input
+        )
+    }
+}
+
+/// A few random edits of `input`.
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1, 4) {
+        let at = rng.index(chars.len() + 1);
+        let len = rng
+            .index(chars.len() - at + 1)
+            .min(rng.range(1, 8) as usize);
+        match rng.below(7) {
+            0 => {
+                chars.drain(at..at + len);
+            }
+            1 if at < chars.len() => chars[at] = DICTIONARY[rng.index(DICTIONARY.len())],
+            2 => chars.insert(at, DICTIONARY[rng.index(DICTIONARY.len())]),
+            3 => {
+                let token = TOKENS[rng.index(TOKENS.len())];
+                chars.splice(at..at, token.chars());
+            }
+            4 => {
+                let copy: Vec<char> = chars[at..at + len].to_vec();
+                chars.splice(at..at, copy);
+            }
+            5 => chars.truncate(at),
+            _ => {
+                // Swap a character with a neighbour
+                if at + 1 < chars.len() {
+                    chars.swap(at, at + 1);
+                }
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// The panic message if parsing `input` panics.
+pub fn parse_panics(solution: &dyn Day, input: &str) -> Option<String> {
+    catch(|| solution.run(input, &[], &mut Plain)).err()
+}
+
+/// Removes lines and then characters while the parser still panics.
+fn minimize(solution: &dyn Day, input: String, message: String) -> (String, String) {
+    let mut input = input;
+    let mut message = message;
+    let mut smaller = |input: &mut String, candidates: Vec<String>| {
+        for candidate in candidates {
+            if let Some(m) = parse_panics(solution, &candidate) {
+                *input = candidate;
+                message = m;
+                return true;
+            }
+        }
+        false
+    };
+
+    loop {
+        let lines: Vec<&str> = input.split('\n').collect();
+        let without_line = (0..lines.len())
+            .map(|i| {
+                let mut lines = lines.clone();
+                lines.remove(i);
+                lines.join("\n")
+            })
+            .collect();
+        if smaller(&mut input, without_line) {
+            continue;
+        }
+
+        let chars: Vec<char> = input.chars().collect();
+        if chars.len() > MAX_MINIMIZE_CHARS {
+            break;
+        }
+        let without_char = (0..chars.len())
+            .map(|i| {
+                let mut chars = chars.clone();
+                chars.remove(i);
+                chars.into_iter().collect()
+            })
+            .collect();
+        if !smaller(&mut input, without_char) {
+            break;
+        }
+    }
+    (input, message)
+}
+
+/// Feeds the parser of `day` with `iterations` mutated inputs and returns the
+/// first one it panics on, minimized.
+pub fn fuzz(day: u32, solution: &dyn Day, iterations: u64, rng: &mut Rng) -> Result<(), Crash> {
+    for _ in 0..iterations {
+        let seed = match rng.below(10) {
+            0 => String::new(),
+            _ => generate(day, rng.range(1, 6) as usize, rng).unwrap_or_default(),
+        };
+        let mut input = mutate(&seed, rng);
+        while rng.chance(0.3) {
+            input = mutate(&input, rng);
+        }
+
+        if let Some(message) = parse_panics(solution, &input) {
+            let (input, message) = minimize(solution, input, message);
+            return Err(Crash {
+                day,
+                input,
+                message,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::registry;
+
+    #[test]
+    fn test_mutate() {
+        let mut rng = Rng::new(1);
+        let inputs: Vec<String> = (0..100).map(|_| mutate("12x5: 1 0 3", &mut rng)).collect();
+        assert!(inputs.iter().any(|i| i != "12x5: 1 0 3"));
+        assert_eq!(inputs, {
+            let mut rng = Rng::new(1);
+            (0..100)
+                .map(|_| mutate("12x5: 1 0 3", &mut rng))
+                .collect::<Vec<_>>()
+        });
+        assert!(mutate("", &mut rng).len() < 30);
+    }
+
+    #[test]
+    fn test_parsers_do_not_panic() {
+        let registry = registry();
+        let crashes: Vec<String> = registry
+            .days()
+            .filter_map(|(day, solution)| {
+                fuzz(day, solution, 2000, &mut Rng::new(day as u64)).err()
+            })
+            .map(|crash| crash.to_string())
+            .collect();
+        assert!(crashes.is_empty(), "{}", crashes.join("\n\n"));
+    }
+}
@@ -0,0 +1,445 @@
+//! Random puzzle inputs in the format of every day, for stress tests and
+//! benchmarks far beyond the size of the real inputs.
+//!
+//! Generation is deterministic for a seed. The `size` of an input is the
+//! number of its lines or items, see [`generate`] for what it means per day.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// SplitMix64, small and good enough for generating inputs.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, `n` must not be zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert_ne!(n, 0);
+        // Multiply-shift instead of modulo, the bias is negligible here
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Uniform in `min..=max`.
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max);
+        min + self.below((max - min) as u64 + 1) as i64
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+}
+
+/// Size of the real input of a day, as a default for [`generate`].
+pub fn default_size(day: u32) -> usize {
+    match day {
+        1 => 4000,
+        2 => 40,
+        3 => 200,
+        4 => 140,
+        5 => 180,
+        6 => 1000,
+        7 => 70,
+        8 => 1000,
+        9 => 500,
+        10 => 180,
+        11 => 600,
+        12 => 1000,
+        _ => 100,
+    }
+}
+
+/// A random input for `day`, `None` if there is no generator for it.
+///
+/// `size` is the number of rotations (day 1), ranges (day 2), battery banks
+/// (day 3), grid rows and columns (day 4), fresh ranges and ids (day 5),
+/// problems (day 6), splitter rows (day 7), junction boxes (day 8), red tiles
+/// (day 9), machines (day 10), devices (day 11) or regions (day 12).
+pub fn generate(day: u32, size: usize, rng: &mut Rng) -> Option<String> {
+    let size = size.max(1);
+    Some(match day {
+        1 => rotations(size, rng),
+        2 => id_ranges(size, rng),
+        3 => battery_banks(size, rng),
+        4 => paper_grid(size, rng),
+        5 => inventory(size, rng),
+        6 => math_problems(size, rng),
+        7 => manifold(size, rng),
+        8 => junction_boxes(size, rng),
+        9 => red_tiles(size, rng),
+        10 => machines(size, rng),
+        11 => devices(size, rng),
+        12 => presents(size, rng),
+        _ => return None,
+    })
+}
+
+fn lines(size: usize, mut line: impl FnMut() -> String) -> String {
+    (0..size).map(|_| line()).collect::<Vec<_>>().join("\n")
+}
+
+fn rotations(size: usize, rng: &mut Rng) -> String {
+    lines(size, || {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        format!("{direction}{}", rng.range(1, 999))
+    })
+}
+
+fn id_ranges(size: usize, rng: &mut Rng) -> String {
+    let ranges: Vec<_> = (0..size)
+        .map(|_| {
+            // At most ten digits, so doubled halves still fit into an i64
+            let min = rng.range(1, 9_999_000_000);
+            format!("{min}-{}", min + rng.range(0, 100_000))
+        })
+        .collect();
+    ranges.join(",")
+}
+
+fn battery_banks(size: usize, rng: &mut Rng) -> String {
+    lines(size, || {
+        (0..100)
+            .map(|_| char::from(b'1' + rng.below(9) as u8))
+            .collect()
+    })
+}
+
+fn paper_grid(size: usize, rng: &mut Rng) -> String {
+    lines(size, || {
+        (0..size)
+            .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+            .collect()
+    })
+}
+
+fn inventory(size: usize, rng: &mut Rng) -> String {
+    const MAX_ID: i64 = 500_000_000_000_000;
+
+    let ranges = lines(size, || {
+        let min = rng.range(1, MAX_ID);
+        format!("{min}-{}", min + rng.range(0, MAX_ID / size as i64))
+    });
+    let ids = lines(size, || rng.range(1, MAX_ID).to_string());
+    format!("{ranges}\n\n{ids}")
+}
+
+fn math_problems(size: usize, rng: &mut Rng) -> String {
+    const ROWS: usize = 4;
+
+    let mut rows = vec![String::new(); ROWS + 1];
+    for problem in 0..size {
+        let numbers: Vec<String> = (0..ROWS)
+            .map(|_| {
+                let digits = rng.range(1, 4) as usize;
+                (0..digits)
+                    .map(|_| char::from(b'1' + rng.below(9) as u8))
+                    .collect()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap();
+
+        // The numbers of a problem are all aligned to the same side
+        let left = rng.chance(0.5);
+        for (row, number) in rows.iter_mut().zip(&numbers) {
+            if problem > 0 {
+                row.push(' ');
+            }
+            if left {
+                write!(row, "{number:<width$}").unwrap();
+            } else {
+                write!(row, "{number:>width$}").unwrap();
+            }
+        }
+
+        let operators = &mut rows[ROWS];
+        if problem > 0 {
+            operators.push(' ');
+        }
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        write!(operators, "{operator:<width$}").unwrap();
+    }
+    rows.join("\n")
+}
+
+fn manifold(size: usize, rng: &mut Rng) -> String {
+    let width = 2 * size + 3;
+    let center = width / 2;
+
+    let mut rows = Vec::new();
+    let mut start = vec!['.'; width];
+    start[center] = 'S';
+    rows.push(start.into_iter().collect());
+
+    for k in 1..=size {
+        rows.push(".".repeat(width));
+        // Splitters of the k-th row are two apart, where a beam may arrive
+        let mut row = vec!['.'; width];
+        for j in 0..k {
+            if rng.chance(0.8) {
+                row[center + 1 - k + 2 * j] = '^';
+            }
+        }
+        rows.push(row.into_iter().collect());
+    }
+    rows.push(".".repeat(width));
+    rows.join("\n")
+}
+
+fn junction_boxes(size: usize, rng: &mut Rng) -> String {
+    lines(size, || {
+        format!(
+            "{},{},{}",
+            rng.range(0, 99_999),
+            rng.range(0, 99_999),
+            rng.range(0, 99_999)
+        )
+    })
+}
+
+/// Corners of a rectilinear outline shaped like a skyline, one corner per line.
+fn red_tiles(size: usize, rng: &mut Rng) -> String {
+    // Two corners per building, plus the two on the ground
+    let buildings = (size / 2).max(2);
+    let mut corners = Vec::new();
+    let mut x = rng.range(1, 1000);
+    let mut height = 0;
+    corners.push((x, 1));
+    for _ in 0..buildings {
+        let previous = height;
+        while height == previous {
+            height = rng.range(2, 99_000);
+        }
+        corners.push((x, height));
+        x += rng.range(1, 99_000 / buildings as i64 + 1);
+        corners.push((x, height));
+    }
+    corners.push((x, 1));
+
+    corners
+        .iter()
+        .map(|(x, y)| format!("{x},{y}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list(values: impl IntoIterator<Item = usize>) -> String {
+    values
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Machines whose lights and joltages come from random button presses, so
+/// both parts have a solution.
+fn machines(size: usize, rng: &mut Rng) -> String {
+    lines(size, || {
+        let lights = rng.range(4, 10) as usize;
+        let num_buttons = rng.range(lights as i64 - 2, lights as i64 + 3) as usize;
+
+        let mut buttons: Vec<BTreeSet<usize>> = (0..num_buttons)
+            .map(|_| {
+                let len = rng.range(1, lights as i64 - 1);
+                (0..len).map(|_| rng.index(lights)).collect()
+            })
+            .collect();
+        // Every counter needs a button to reach its joltage
+        for light in 0..lights {
+            if !buttons.iter().any(|b| b.contains(&light)) {
+                let button = rng.index(num_buttons);
+                buttons[button].insert(light);
+            }
+        }
+
+        let mut indicators = vec![false; lights];
+        while !indicators.contains(&true) {
+            for button in &buttons {
+                if rng.chance(0.5) {
+                    for &light in button {
+                        indicators[light] = !indicators[light];
+                    }
+                }
+            }
+        }
+
+        let mut joltages = vec![0; lights];
+        for button in &buttons {
+            let presses = rng.range(0, 40) as usize;
+            for &light in button {
+                joltages[light] += presses;
+            }
+        }
+
+        let indicators: String = indicators
+            .iter()
+            .map(|&on| if on { '#' } else { '.' })
+            .collect();
+        let buttons: Vec<String> = buttons
+            .iter()
+            .map(|b| format!("({})", list(b.iter().copied())))
+            .collect();
+        format!(
+            "[{indicators}] {} {{{}}}",
+            buttons.join(" "),
+            list(joltages)
+        )
+    })
+}
+
+/// A device graph without cycles. `svr` comes first, `fft` and `dac` are in
+/// between, and the number of paths is kept small enough to count.
+fn devices(size: usize, rng: &mut Rng) -> String {
+    const MAX_PATHS: u64 = 1_000_000_000_000;
+
+    let mut names = BTreeSet::from(["svr", "you", "fft", "dac", "out"].map(String::from));
+    let mut order = vec!["svr".to_string(), "you".to_string()];
+    while order.len() < size.max(3) + 2 {
+        let name: String = (0..3)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if names.insert(name.clone()) {
+            order.push(name);
+        }
+    }
+    let third = order.len() / 3;
+    order.insert(third.max(2), "fft".to_string());
+    order.insert(2 * third + 1, "dac".to_string());
+    order.push("out".to_string());
+
+    // Built from the back, counting the paths to `out` from every device
+    let last = order.len() - 1;
+    let mut paths = vec![0u64; order.len()];
+    paths[last] = 1;
+    let mut outputs = vec![Vec::new(); order.len()];
+    for i in (0..last).rev() {
+        let mut targets = vec![i + 1];
+        for _ in 0..rng.range(0, 2) {
+            targets.push((i + 1 + rng.index(8)).min(last));
+        }
+        targets.sort();
+        targets.dedup();
+
+        for target in targets {
+            if outputs[i].is_empty() || paths[i] + paths[target] <= MAX_PATHS {
+                paths[i] += paths[target];
+                outputs[i].push(target);
+            }
+        }
+    }
+
+    (0..last)
+        .map(|i| {
+            let outputs: Vec<&str> = outputs[i].iter().map(|&o| order[o].as_str()).collect();
+            format!("{}: {}", order[i], outputs.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn presents(size: usize, rng: &mut Rng) -> String {
+    const SHAPES: usize = 6;
+
+    let mut out = String::new();
+    for shape in 0..SHAPES {
+        // At least five of the nine cells are part of the present
+        let mut cells = [true; 9];
+        for _ in 0..rng.range(0, 4) {
+            cells[rng.index(9)] = false;
+        }
+        writeln!(out, "{shape}:").unwrap();
+        for row in cells.chunks(3) {
+            let row: String = row.iter().map(|&c| if c { '#' } else { '.' }).collect();
+            writeln!(out, "{row}").unwrap();
+        }
+        out.push('\n');
+    }
+
+    let regions = lines(size, || {
+        let (width, height) = (rng.range(4, 50), rng.range(4, 50));
+        // Around as many presents as fit, so some regions are too small
+        let capacity = (width * height / 7) as usize;
+        let mut counts = [0; SHAPES];
+        for _ in 0..rng.range(capacity as i64 / 2, capacity as i64 * 5 / 4) {
+            counts[rng.index(SHAPES)] += 1;
+        }
+        format!(
+            "{width}x{height}: {}",
+            counts.map(|c| c.to_string()).join(" ")
+        )
+    });
+    out + &regions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::registry;
+    use crate::solution::Plain;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(42);
+        let values: Vec<_> = (0..1000).map(|_| rng.range(-3, 3)).collect();
+        assert!((-3..=3).all(|v| values.contains(&v)));
+        assert!(values.iter().all(|v| (-3..=3).contains(v)));
+
+        assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn test_inputs_parse() {
+        let registry = registry();
+        for (day, solution) in registry.days() {
+            for seed in 0..5 {
+                for size in [1, 3, 50] {
+                    let input = generate(day, size, &mut Rng::new(seed)).unwrap();
+                    if let Err(e) = solution.run(&input, &[], &mut Plain) {
+                        panic!("Day {day}, seed {seed}, size {size}: {e}\n{input}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let input = generate(10, 20, &mut Rng::new(3));
+        assert_eq!(input, generate(10, 20, &mut Rng::new(3)));
+        assert_ne!(input, generate(10, 20, &mut Rng::new(4)));
+    }
+
+    #[test]
+    fn test_day11_is_acyclic() {
+        let input = generate(11, 200, &mut Rng::new(1)).unwrap();
+        let mut seen = BTreeSet::new();
+        // Every device is listed before the ones it outputs to
+        for line in input.lines().rev() {
+            let (name, outputs) = line.split_once(": ").unwrap();
+            assert!(outputs.split(' ').all(|o| o == "out" || seen.contains(o)));
+            seen.insert(name);
+        }
+        assert!(
+            ["svr", "you", "fft", "dac"]
+                .iter()
+                .all(|n| seen.contains(n))
+        );
+    }
+}
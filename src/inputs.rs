@@ -0,0 +1,170 @@
+//! Finding puzzle inputs on disk.
+//!
+//! Inputs live in an input directory, `inputs` unless overridden with
+//! `--input-dir` or the `AOC25_INPUTS` environment variable. The real input of
+//! a day is `day5.txt`, other named inputs are either `day5.<name>.txt` or
+//! `day5/<name>.txt`, e.g. `day5.alt.txt` or `day5/example1.txt`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const INPUT_DIR: &str = "inputs";
+pub const INPUT_DIR_ENV: &str = "AOC25_INPUTS";
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InputLocator {
+    pub dir: PathBuf,
+    /// Named input to use instead of the real input
+    pub name: Option<String>,
+}
+
+impl InputLocator {
+    pub fn new(dir: impl Into<PathBuf>, name: Option<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            name,
+        }
+    }
+
+    /// Uses `dir` if given, otherwise the directory from the environment or
+    /// the default.
+    pub fn from_env(dir: Option<&Path>, name: Option<String>) -> Self {
+        let dir = dir
+            .map(Path::to_path_buf)
+            .or_else(|| env::var_os(INPUT_DIR_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(INPUT_DIR));
+        Self::new(dir, name)
+    }
+
+    /// Paths the input of `day` may be at, in order of preference.
+    pub fn candidates(&self, day: u32) -> Vec<PathBuf> {
+        match &self.name {
+            None => vec![self.dir.join(format!("day{day}.txt"))],
+            Some(name) => vec![
+                self.dir.join(format!("day{day}.{name}.txt")),
+                self.dir
+                    .join(format!("day{day}"))
+                    .join(format!("{name}.txt")),
+            ],
+        }
+    }
+
+    /// Names of the inputs available for `day`, `None` being the real input.
+    pub fn available(&self, day: u32) -> Vec<Option<String>> {
+        let txt_stem = |path: &Path| {
+            let file_name = path.file_name()?.to_str()?;
+            Some(file_name.strip_suffix(".txt")?.to_string())
+        };
+        let files = |dir: &Path| -> Vec<String> {
+            fs::read_dir(dir)
+                .map(|entries| entries.filter_map(|e| txt_stem(&e.ok()?.path())).collect())
+                .unwrap_or_default()
+        };
+
+        let prefix = format!("day{day}");
+        let mut names: Vec<_> = files(&self.dir)
+            .into_iter()
+            .filter_map(|stem| match stem.strip_prefix(&prefix)? {
+                "" => Some(None),
+                rest => Some(Some(rest.strip_prefix('.')?.to_string())),
+            })
+            .chain(files(&self.dir.join(&prefix)).into_iter().map(Some))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Finds the input of `day`, explaining where it was expected if missing.
+    pub fn find(&self, day: u32) -> Result<PathBuf, String> {
+        let candidates = self.candidates(day);
+        if let Some(path) = candidates.iter().find(|p| p.is_file()) {
+            return Ok(path.clone());
+        }
+
+        let expected: Vec<_> = candidates.iter().map(|p| p.display().to_string()).collect();
+        let mut message = match &self.name {
+            None => format!("No input for day {day}, expected {}", expected[0]),
+            Some(name) => format!(
+                "No input `{name}` for day {day}, expected {}",
+                expected.join(" or ")
+            ),
+        };
+
+        if !self.dir.is_dir() {
+            let cwd = env::current_dir().map_or(String::new(), |d| format!(" in {}", d.display()));
+            message += &format!(
+                "\nThe input directory {} does not exist{cwd}, set it with --input-dir or {INPUT_DIR_ENV}",
+                self.dir.display()
+            );
+        } else {
+            let available: Vec<_> = self
+                .available(day)
+                .into_iter()
+                .map(|n| n.unwrap_or_else(|| "(real input)".to_string()))
+                .collect();
+            if !available.is_empty() {
+                message += &format!("\nAvailable inputs for day {day}: {}", available.join(", "));
+            }
+        }
+
+        Err(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates() {
+        let locator = InputLocator::new("inputs", None);
+        assert_eq!(
+            vec![PathBuf::from("inputs/day4.txt")],
+            locator.candidates(4)
+        );
+
+        let locator = InputLocator::new("puzzles", Some("example1".to_string()));
+        assert_eq!(
+            vec![
+                PathBuf::from("puzzles/day5.example1.txt"),
+                PathBuf::from("puzzles/day5/example1.txt")
+            ],
+            locator.candidates(5)
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let dir = env::temp_dir().join(format!("aoc25-inputs-{}", std::process::id()));
+        fs::create_dir_all(dir.join("day5")).unwrap();
+        fs::write(dir.join("day5.txt"), "").unwrap();
+        fs::write(dir.join("day5.alt.txt"), "").unwrap();
+        fs::write(dir.join("day5/example1.txt"), "").unwrap();
+        fs::write(dir.join("day50.txt"), "").unwrap();
+
+        let locator = InputLocator::new(&dir, None);
+        assert_eq!(Ok(dir.join("day5.txt")), locator.find(5));
+        assert_eq!(
+            vec![None, Some("alt".to_string()), Some("example1".to_string())],
+            locator.available(5)
+        );
+
+        let example = InputLocator::new(&dir, Some("example1".to_string()));
+        assert_eq!(Ok(dir.join("day5/example1.txt")), example.find(5));
+
+        let missing = InputLocator::new(&dir, Some("example2".to_string()))
+            .find(5)
+            .unwrap_err();
+        assert!(missing.contains("day5.example2.txt or "));
+        assert!(missing.contains("Available inputs for day 5: (real input), alt, example1"));
+
+        let missing = InputLocator::new(dir.join("nope"), None)
+            .find(5)
+            .unwrap_err();
+        assert!(missing.contains("does not exist"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
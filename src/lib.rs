@@ -0,0 +1,33 @@
+//! Solutions to Advent of Code 2025 and the utilities they are built on.
+//!
+//! Every day implements [`solution::Solution`] and is listed in
+//! [`registry::registry`]. The `aoc25` binary is a command line runner on top.
+
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+pub mod alloc;
+pub mod answers;
+pub mod baseline;
+pub mod day1;
+pub mod day10;
+pub mod day10_2;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod examples;
+pub mod fuzz;
+pub mod generate;
+pub mod inputs;
+pub mod parse;
+pub mod registry;
+pub mod report;
+pub mod solution;
+pub mod timed;
+pub mod util;
@@ -1,40 +1,139 @@
-#![allow(warnings)]
-#![feature(portable_simd)]
+use std::{env, fs, process};
 
-use crate::day1::day1;
-use crate::day2::day2;
-use crate::day3::day3;
-use crate::day4::day4;
-use crate::day5::day5;
-use crate::day6::day6;
-use crate::day7::day7;
-use crate::day8::day8;
-use crate::day9::day9;
-use crate::day10::day10;
-use crate::day11::day11;
-use crate::day12::day12;
+use aoc25::generate::{self, Rng};
+use aoc25::inputs::InputLocator;
+use aoc25::registry::registry;
 
-mod day1;
-mod day10;
-mod day10_2;
-mod day11;
-mod day12;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod timed;
-mod util;
+use crate::cli::{BaselineAction, Command};
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static ALLOCATOR: aoc25::alloc::CountingAllocator = aoc25::alloc::CountingAllocator;
+
+mod cli;
+mod runner;
+mod scaffold;
+mod watch;
 
-// TODO: Slow days
-// 2 p1 & p2
-// 4 p2
-// 8 p1 & p2
-// 10 p1 & p2
 fn main() {
-    day12();
+    let command = match cli::parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let registry = registry();
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Run {
+            run,
+            report,
+            jobs,
+            limits,
+        } => {
+            if let Err(e) = runner::run(&registry, &run, report.as_ref(), jobs, &limits) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        Command::Bench { run, config } => runner::bench(&registry, &run, &config),
+        Command::Baseline {
+            run,
+            config,
+            file,
+            action,
+        } => {
+            let result = match action {
+                BaselineAction::Save => {
+                    runner::save_baseline(&registry, &run, &config, &file).map(|_| true)
+                }
+                BaselineAction::Compare { threshold, limits } => {
+                    runner::compare_baseline(&registry, &run, &config, &file, threshold, &limits)
+                }
+            };
+            match result {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Verify { days, input_dir } => {
+            let locator = InputLocator::from_env(input_dir.as_deref(), None);
+            if !runner::verify(&registry, &days, &locator) {
+                process::exit(1);
+            }
+        }
+        Command::Compare { run } => match runner::compare(&registry, &run) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        },
+        Command::Answer { day, part, record } => {
+            if let Err(e) = runner::record_answer(day, part, record) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        Command::Examples { day, dir, check } => {
+            match runner::examples(&registry, day, &dir, check) {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Watch { run } => {
+            if let Err(e) = watch::watch(&run) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+        Command::Fuzz {
+            days,
+            iterations,
+            seed,
+        } => {
+            if !runner::fuzz(&registry, &days, iterations, seed) {
+                process::exit(1);
+            }
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let size = size.unwrap_or_else(|| generate::default_size(day));
+            let Some(input) = generate::generate(day, size, &mut Rng::new(seed)) else {
+                eprintln!("No generator for day {day}");
+                process::exit(1);
+            };
+            match output {
+                Some(path) => {
+                    if let Err(e) = fs::write(&path, input + "\n") {
+                        eprintln!("Could not write {}: {e}", path.display());
+                        process::exit(1);
+                    }
+                }
+                None => println!("{input}"),
+            }
+        }
+        Command::New { day, dir } => {
+            if let Err(e) = scaffold::new_day(day, &dir) {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
 }
@@ -0,0 +1,202 @@
+//! Errors for malformed puzzle inputs that point at where the input went wrong.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Filled in by the runner, parsers do not know their day
+    pub day: Option<u32>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The whole line the error is on
+    pub snippet: String,
+    /// Number of characters to underline, at least 1
+    pub len: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `part`, which must be a slice of `input`.
+    pub fn new(input: &str, part: &str, message: impl Into<String>) -> Self {
+        Self::at(input, offset(input, part), part.len(), message)
+    }
+
+    /// Creates an error pointing at `len` bytes from the byte `offset`.
+    pub fn at(input: &str, offset: usize, len: usize, message: impl Into<String>) -> Self {
+        let floor = |mut i: usize| {
+            i = i.min(input.len());
+            while !input.is_char_boundary(i) {
+                i -= 1;
+            }
+            i
+        };
+        let start = floor(offset);
+        let end = floor(offset + len);
+
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let underlined = &input[start..end.min(line_end)];
+
+        Self {
+            day: None,
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            len: underlined.chars().count().max(1),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for input that ended too early.
+    pub fn end(input: &str, message: impl Into<String>) -> Self {
+        Self::at(input, input.len(), 0, message)
+    }
+
+    pub fn with_day(self, day: u32) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    /// Multi-line description with the offending line and a caret below the
+    /// offending part.
+    pub fn diagnostic(&self) -> String {
+        let gutter = self.line.to_string().len();
+        let location = match self.day {
+            Some(day) => format!("day {day}, line {}, column {}", self.line, self.column),
+            None => format!("line {}, column {}", self.line, self.column),
+        };
+
+        format!(
+            "error: {}\n{:gutter$}--> {location}\n{:gutter$} |\n{} | {}\n{:gutter$} | {:indent$}{}\n",
+            self.message,
+            "",
+            "",
+            self.line,
+            self.snippet,
+            "",
+            "",
+            "^".repeat(self.len),
+            indent = self.column - 1
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Byte offset of `part`, which must be a slice of `input`.
+pub fn offset(input: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    debug_assert!(
+        offset + part.len() <= input.len(),
+        "Error location is not part of the input"
+    );
+    offset
+}
+
+/// Parses `s`, a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| {
+        if s.is_empty() {
+            ParseError::new(input, s, "Expected a number")
+        } else {
+            ParseError::new(input, s, format!("Expected a number, found `{s}`"))
+        }
+    })
+}
+
+/// Checks that `lines`, slices of `input`, form a rectangle of `allowed`
+/// characters.
+pub fn check_grid<'a>(
+    input: &str,
+    lines: impl IntoIterator<Item = &'a str>,
+    allowed: &str,
+) -> Result<(), ParseError> {
+    let mut width = None;
+    for line in lines {
+        if let Some((i, c)) = line.char_indices().find(|&(_, c)| !allowed.contains(c)) {
+            let message = format!("Unknown cell `{c}`, expected one of `{allowed}`");
+            return Err(ParseError::new(input, &line[i..i + c.len_utf8()], message));
+        }
+        match width {
+            None => width = Some(line.len()),
+            Some(w) if w != line.len() => {
+                let message = format!("Expected a row of width {w}, found {}", line.len());
+                return Err(ParseError::new(input, line, message));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "1,2,3\n4,x5,6\n";
+        let line = input.lines().nth(1).unwrap();
+        let error = parse_number::<i64>(input, line.split(',').nth(1).unwrap()).unwrap_err();
+
+        assert_eq!(2, error.line);
+        assert_eq!(3, error.column);
+        assert_eq!("4,x5,6", error.snippet);
+        assert_eq!(2, error.len);
+        assert_eq!(
+            "day 8, line 2, column 3: Expected a number, found `x5`",
+            error.clone().with_day(8).to_string()
+        );
+        assert_eq!(
+            "error: Expected a number, found `x5`
+ --> line 2, column 3
+  |
+2 | 4,x5,6
+  |   ^^
+",
+            error.diagnostic()
+        );
+    }
+
+    #[test]
+    fn test_end() {
+        let error = ParseError::end("ab\ncd", "Unexpected end of input");
+        assert_eq!((2, 3, 1), (error.line, error.column, error.len));
+        assert_eq!("cd", error.snippet);
+
+        let error = ParseError::at("é,x", 1, 1, "Bad");
+        assert_eq!((1, 1), (error.line, error.column));
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "..#\n#..";
+        assert_eq!(Ok(()), check_grid(input, input.lines(), ".#"));
+
+        let input = "..#\n#é.";
+        let error = check_grid(input, input.lines(), ".#").unwrap_err();
+        assert_eq!((2, 2), (error.line, error.column));
+
+        let input = "..#\n#.";
+        let error = check_grid(input, input.lines(), ".#").unwrap_err();
+        assert_eq!((2, 1, 2), (error.line, error.column, error.len));
+    }
+}
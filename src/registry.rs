@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use crate::day1::Day1;
+use crate::day2::Day2;
+use crate::day3::Day3;
+use crate::day4::Day4;
+use crate::day5::Day5;
+use crate::day6::Day6;
+use crate::day7::Day7;
+use crate::day8::Day8;
+use crate::day9::Day9;
+use crate::day10::Day10;
+use crate::day11::Day11;
+use crate::day12::Day12;
+use crate::parse::ParseError;
+use crate::solution::{Day, Part, Plain, Solution};
+use crate::{day2, day10_2};
+
+/// Parses the input and solves one part, returning the answer or `None` if
+/// the input is larger than the implementation takes.
+pub type PartFn = fn(&str) -> Result<Option<String>, ParseError>;
+
+/// Another implementation of a part, cross-checked against the registered
+/// solution by `compare`. Slow but simple ones serve as oracles.
+pub struct Alternate {
+    pub day: u32,
+    pub part: Part,
+    pub name: &'static str,
+    pub run: PartFn,
+}
+
+/// A way to compute a part, the registered solution or an alternate.
+pub enum Implementation<'a> {
+    Default(&'a dyn Day, Part),
+    Alternate(&'a Alternate),
+}
+
+impl Implementation<'_> {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Implementation::Default(..) => "default",
+            Implementation::Alternate(alternate) => alternate.name,
+        }
+    }
+
+    /// The answer, `None` if an alternate does not take inputs this large.
+    pub fn run(&self, input: &str) -> Result<Option<String>, ParseError> {
+        match self {
+            Implementation::Default(solution, part) => {
+                let answers = solution.run(input, &[*part], &mut Plain)?;
+                Ok(Some(answers[0].answer.clone()))
+            }
+            Implementation::Alternate(alternate) => (alternate.run)(input),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<u32, Box<dyn Day>>,
+    alternates: Vec<Alternate>,
+}
+
+impl Registry {
+    pub fn register(&mut self, day: u32, solution: impl Day + 'static) {
+        let previous = self.days.insert(day, Box::new(solution));
+        assert!(previous.is_none(), "Day {day} registered twice");
+    }
+
+    pub fn get(&self, day: u32) -> Option<&dyn Day> {
+        self.days.get(&day).map(|d| d.as_ref())
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = (u32, &dyn Day)> {
+        self.days.iter().map(|(&day, d)| (day, d.as_ref()))
+    }
+
+    pub fn register_alternate(&mut self, day: u32, part: Part, name: &'static str, run: PartFn) {
+        assert!(
+            !self.alternates(day, part).any(|a| a.name == name),
+            "Alternate {name} of day {day} part {part} registered twice"
+        );
+        self.alternates.push(Alternate {
+            day,
+            part,
+            name,
+            run,
+        });
+    }
+
+    pub fn alternates(&self, day: u32, part: Part) -> impl Iterator<Item = &Alternate> {
+        self.alternates
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// The registered solution of a part followed by its alternates. Empty if
+    /// the day is not registered.
+    pub fn implementations(&self, day: u32, part: Part) -> Vec<Implementation<'_>> {
+        let Some(solution) = self.get(day) else {
+            return Vec::new();
+        };
+
+        [Implementation::Default(solution, part)]
+            .into_iter()
+            .chain(self.alternates(day, part).map(Implementation::Alternate))
+            .collect()
+    }
+}
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+
+    registry.register(1, Day1::default());
+    registry.register(2, Day2);
+    registry.register(3, Day3);
+    registry.register(4, Day4);
+    registry.register(5, Day5);
+    registry.register(6, Day6);
+    registry.register(7, Day7);
+    registry.register(8, Day8::default());
+    registry.register(9, Day9);
+    registry.register(10, Day10);
+    registry.register(11, Day11);
+    registry.register(12, Day12);
+
+    registry.register_alternate(2, Part::Two, "is_repeated_str", |input| {
+        Ok(Some(day2::part2_str(&Day2.parse(input)?).to_string()))
+    });
+    registry.register_alternate(10, Part::Two, "brute_force", |input| {
+        Ok(day10_2::part2(&Day10.parse(input)?).map(|p| p.to_string()))
+    });
+
+    registry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::PartAnswer;
+
+    #[test]
+    fn test_all_days_registered() {
+        // Later days may be scaffolded before the ones in between
+        let days: Vec<_> = registry().days().map(|(day, _)| day).collect();
+        assert!(!days.is_empty());
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{days:?}");
+    }
+
+    #[test]
+    fn test_run_through_registry() {
+        let registry = registry();
+        let day1 = registry.get(1).unwrap();
+
+        let answers = day1
+            .run(
+                "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82",
+                &Part::ALL,
+                &mut Plain,
+            )
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                PartAnswer {
+                    part: Part::One,
+                    answer: "3".to_string()
+                },
+                PartAnswer {
+                    part: Part::Two,
+                    answer: "6".to_string()
+                },
+            ],
+            answers
+        );
+    }
+
+    #[test]
+    fn test_alternates_agree() {
+        let registry = registry();
+        let check = |day, part, input| {
+            let answers: Vec<_> = registry
+                .implementations(day, part)
+                .iter()
+                .map(|i| (i.name(), i.run(input).unwrap()))
+                .collect();
+            assert!(answers.len() > 1);
+            assert!(
+                answers.iter().all(|(_, a)| *a == answers[0].1),
+                "Day {day} part {part} disagrees: {answers:?}"
+            );
+        };
+
+        check(2, Part::Two, "11-22,95-115,998-1012,1188511880-1188511890");
+        check(
+            10,
+            Part::Two,
+            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+        );
+        assert!(registry.implementations(1, Part::One).len() == 1);
+    }
+}
@@ -0,0 +1,426 @@
+//! Machine readable reports of a run, for tracking performance over time.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::alloc::AllocStats;
+use crate::solution::{Part, PartAnswer, Stage};
+use crate::timed::{Profile, SpanStats, Timed};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("Unknown report format `{s}`, expected json or csv")),
+        }
+    }
+}
+
+impl ReportFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+/// Where a run came from, so reports from different commits and machines can
+/// be told apart.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Metadata {
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub host: Option<String>,
+    pub os: String,
+    pub arch: String,
+}
+
+impl Metadata {
+    pub fn collect() -> Self {
+        let commit = process::Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|c| c.trim().to_string());
+
+        let host = env::var("HOSTNAME")
+            .ok()
+            .or_else(|| fs::read_to_string("/etc/hostname").ok())
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty());
+
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |t| t.as_secs()),
+            commit,
+            host,
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ReportEntry {
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    /// Time to parse the input, shared by both parts of a day
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// Only counted when built with the counting allocator
+    pub parse_allocs: Option<AllocStats>,
+    pub solve_allocs: Option<AllocStats>,
+    /// Spans recorded while solving, nested below the part itself
+    pub spans: Vec<SpanStats>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Report {
+    pub metadata: Metadata,
+    pub entries: Vec<ReportEntry>,
+}
+
+fn json_string(s: &str) -> String {
+    let mut res = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(res, "\\u{:04x}", c as u32).unwrap(),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json_option(s: &Option<String>) -> String {
+    s.as_deref().map_or("null".to_string(), json_string)
+}
+
+fn json_allocs(stats: Option<AllocStats>) -> String {
+    stats.map_or("null".to_string(), |s| {
+        format!(
+            "{{\"allocations\": {}, \"bytes\": {}, \"peak\": {}}}",
+            s.allocations, s.bytes, s.peak
+        )
+    })
+}
+
+/// Allocations, bytes and peak as three fields, empty if not counted.
+fn csv_allocs(stats: Option<AllocStats>) -> String {
+    stats.map_or(",,".to_string(), |s| {
+        format!("{},{},{}", s.allocations, s.bytes, s.peak)
+    })
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', ';']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Splits a line written by `to_csv` back into its fields.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Day and answer of every entry of a CSV report.
+pub fn answers_from_csv(csv: &str) -> Result<Vec<(u32, PartAnswer)>, String> {
+    csv.lines()
+        .skip(1)
+        .map(|line| {
+            let fields = csv_fields(line);
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.get(3), fields.get(4), fields.get(5))
+            else {
+                return Err(format!("Malformed report line `{line}`"));
+            };
+            let day = day
+                .parse()
+                .map_err(|_| format!("Invalid day `{day}` in report"))?;
+            let answer = PartAnswer {
+                part: part.parse()?,
+                answer: answer.clone(),
+            };
+            Ok((day, answer))
+        })
+        .collect()
+}
+
+impl Report {
+    pub fn new(metadata: Metadata) -> Self {
+        Self {
+            metadata,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds the results of running one day, using the times measured by the
+    /// harness and the spans recorded while it ran.
+    pub fn add_day(
+        &mut self,
+        day: u32,
+        answers: &[PartAnswer],
+        timed: &Timed,
+        profile: Option<&Profile>,
+    ) {
+        let parse_time = timed.time(Stage::Parse).unwrap_or_default();
+        let parse_allocs = timed.allocs(Stage::Parse);
+
+        for answer in answers {
+            let stage = Stage::Solve(answer.part);
+            self.entries.push(ReportEntry {
+                day,
+                part: answer.part,
+                answer: answer.answer.clone(),
+                parse_time,
+                solve_time: timed.time(stage).unwrap_or_default(),
+                parse_allocs,
+                solve_allocs: timed.allocs(stage),
+                spans: profile.map_or(Vec::new(), |p| p.subtree(&stage.to_string()).spans),
+            });
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let m = &self.metadata;
+        let mut json = String::new();
+
+        writeln!(json, "{{").unwrap();
+        writeln!(json, "  \"timestamp\": {},", m.timestamp).unwrap();
+        writeln!(json, "  \"commit\": {},", json_option(&m.commit)).unwrap();
+        writeln!(
+            json,
+            "  \"machine\": {{\"host\": {}, \"os\": {}, \"arch\": {}}},",
+            json_option(&m.host),
+            json_string(&m.os),
+            json_string(&m.arch)
+        )
+        .unwrap();
+        writeln!(json, "  \"entries\": [").unwrap();
+
+        for (i, e) in self.entries.iter().enumerate() {
+            let spans: Vec<_> = e
+                .spans
+                .iter()
+                .map(|s| {
+                    format!(
+                        "{{\"name\": {}, \"depth\": {}, \"total_ns\": {}, \"self_ns\": {}, \"calls\": {}}}",
+                        json_string(&s.name),
+                        s.depth,
+                        s.total.as_nanos(),
+                        s.self_time.as_nanos(),
+                        s.calls
+                    )
+                })
+                .collect();
+
+            write!(
+                json,
+                "    {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"parse_allocs\": {}, \"solve_allocs\": {}, \"spans\": [{}]}}",
+                e.day,
+                e.part,
+                json_string(&e.answer),
+                e.parse_time.as_nanos(),
+                e.solve_time.as_nanos(),
+                json_allocs(e.parse_allocs),
+                json_allocs(e.solve_allocs),
+                spans.join(", ")
+            )
+            .unwrap();
+            writeln!(
+                json,
+                "{}",
+                if i + 1 < self.entries.len() { "," } else { "" }
+            )
+            .unwrap();
+        }
+
+        writeln!(json, "  ]").unwrap();
+        writeln!(json, "}}").unwrap();
+        json
+    }
+
+    /// One row per part, the allocation columns are empty unless counted.
+    /// Spans are listed as `path=total_ns` pairs separated by `;`, where the
+    /// path joins nested span names with `/`.
+    pub fn to_csv(&self) -> String {
+        let m = &self.metadata;
+        let mut csv = String::from(
+            "timestamp,commit,host,day,part,answer,parse_ns,solve_ns,\
+             parse_allocations,parse_bytes,parse_peak,solve_allocations,solve_bytes,solve_peak,\
+             spans\n",
+        );
+
+        for e in &self.entries {
+            let mut path: Vec<&str> = Vec::new();
+            let spans: Vec<_> = e
+                .spans
+                .iter()
+                .map(|s| {
+                    path.truncate(s.depth);
+                    path.push(&s.name);
+                    format!("{}={}", path.join("/"), s.total.as_nanos())
+                })
+                .collect();
+
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                m.timestamp,
+                csv_field(m.commit.as_deref().unwrap_or("")),
+                csv_field(m.host.as_deref().unwrap_or("")),
+                e.day,
+                e.part,
+                csv_field(&e.answer),
+                e.parse_time.as_nanos(),
+                e.solve_time.as_nanos(),
+                csv_allocs(e.parse_allocs),
+                csv_allocs(e.solve_allocs),
+                csv_field(&spans.join(";"))
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv => self.to_csv(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        let metadata = Metadata {
+            timestamp: 1765000000,
+            commit: Some("abc1234".to_string()),
+            host: None,
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+        };
+        let span = |name: &str, depth, nanos| SpanStats {
+            name: name.to_string(),
+            depth,
+            total: Duration::from_nanos(nanos),
+            self_time: Duration::from_nanos(nanos),
+            calls: 1,
+        };
+
+        Report {
+            metadata,
+            entries: vec![ReportEntry {
+                day: 8,
+                part: Part::One,
+                answer: "40".to_string(),
+                parse_time: Duration::from_nanos(1200),
+                solve_time: Duration::from_nanos(5000),
+                parse_allocs: None,
+                solve_allocs: Some(AllocStats {
+                    allocations: 3,
+                    bytes: 4096,
+                    peak: 2048,
+                }),
+                spans: vec![span("Find pairs", 0, 3000), span("Heap \"push\"", 1, 100)],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            r#"{
+  "timestamp": 1765000000,
+  "commit": "abc1234",
+  "machine": {"host": null, "os": "linux", "arch": "x86_64"},
+  "entries": [
+    {"day": 8, "part": 1, "answer": "40", "parse_ns": 1200, "solve_ns": 5000, "parse_allocs": null, "solve_allocs": {"allocations": 3, "bytes": 4096, "peak": 2048}, "spans": [{"name": "Find pairs", "depth": 0, "total_ns": 3000, "self_ns": 3000, "calls": 1}, {"name": "Heap \"push\"", "depth": 1, "total_ns": 100, "self_ns": 100, "calls": 1}]}
+  ]
+}
+"#,
+            report().to_json()
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            "timestamp,commit,host,day,part,answer,parse_ns,solve_ns,\
+             parse_allocations,parse_bytes,parse_peak,solve_allocations,solve_bytes,solve_peak,spans
+1765000000,abc1234,,8,1,40,1200,5000,,,,3,4096,2048,\"Find pairs=3000;Find pairs/Heap \"\"push\"\"=100\"
+",
+            report().to_csv()
+        );
+    }
+
+    #[test]
+    fn test_answers_from_csv() {
+        let mut report = report();
+        report.entries[0].answer = "a,\"b\"".to_string();
+
+        assert_eq!(
+            Ok(vec![(
+                8,
+                PartAnswer {
+                    part: Part::One,
+                    answer: "a,\"b\"".to_string()
+                }
+            )]),
+            answers_from_csv(&report.to_csv())
+        );
+        assert!(answers_from_csv("header\n1,2").is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Ok(ReportFormat::Csv), "csv".parse());
+        assert_eq!(
+            Some(ReportFormat::Json),
+            ReportFormat::from_path(Path::new("runs/report.json"))
+        );
+        assert_eq!(None, ReportFormat::from_path(Path::new("report.txt")));
+    }
+}
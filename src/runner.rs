@@ -0,0 +1,629 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli::{ReportArgs, RunArgs};
+use aoc25::alloc::format_bytes;
+use aoc25::answers::{ANSWERS_DIR, AnswerFile, Check, Record};
+use aoc25::baseline::{self, Baseline, TimeLimits};
+use aoc25::examples::{self, Puzzle};
+use aoc25::fuzz;
+use aoc25::generate::Rng;
+use aoc25::inputs::InputLocator;
+use aoc25::parse::ParseError;
+use aoc25::registry::Registry;
+use aoc25::report::{Metadata, Report};
+use aoc25::solution::{Day, Part, PartAnswer, Plain, Stage};
+use aoc25::timed::{
+    Bench, BenchConfig, Profile, Timed, enable_profiling, format_duration, take_profile,
+};
+use aoc25::util::panic::catch;
+
+fn load_answers(day: u32) -> Option<AnswerFile> {
+    match AnswerFile::load(Path::new(ANSWERS_DIR), day) {
+        Ok(answers) => Some(answers),
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    }
+}
+
+/// Why a day could not produce its answers.
+enum DayError {
+    Input(String),
+    Parse(ParseError),
+    Panic(String),
+}
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayError::Input(e) => write!(f, "{e}"),
+            DayError::Parse(e) => write!(
+                f,
+                "invalid input on line {}, column {}: {}",
+                e.line, e.column, e.message
+            ),
+            DayError::Panic(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// What running one day produced, or why it could not finish.
+struct DayRun {
+    day: u32,
+    unfinished: &'static [Part],
+    outcome: Result<(Vec<PartAnswer>, Timed, Option<Profile>), DayError>,
+}
+
+/// Runs a single day, turning a missing input or a panic into an error.
+fn run_day(solution: &dyn Day, day: u32, args: &RunArgs, quiet: bool, profile: bool) -> DayRun {
+    let run = || {
+        let path = args.input_path(day).map_err(DayError::Input)?;
+        let input = fs::read_to_string(&path)
+            .map_err(|e| DayError::Input(format!("Could not read {}: {e}", path.display())))?;
+
+        if profile {
+            enable_profiling();
+        }
+        let mut timed = Timed {
+            quiet,
+            ..Timed::default()
+        };
+        let answers = catch(|| solution.run(&input, &args.parts, &mut timed));
+        let profile = take_profile();
+
+        match answers {
+            Ok(Ok(answers)) => Ok((answers, timed, profile)),
+            Ok(Err(e)) => Err(DayError::Parse(e.with_day(day))),
+            Err(e) => Err(DayError::Panic(e)),
+        }
+    };
+
+    DayRun {
+        day,
+        unfinished: solution.unfinished(),
+        outcome: run(),
+    }
+}
+
+/// Runs the days on up to `jobs` threads, returning the runs ordered by day.
+fn run_days(
+    registry: &Registry,
+    args: &RunArgs,
+    jobs: usize,
+    quiet: bool,
+    profile: bool,
+) -> Vec<DayRun> {
+    let days: Vec<_> = args
+        .days
+        .iter()
+        .filter_map(|&day| match registry.get(day) {
+            Some(solution) => Some((day, solution)),
+            None => {
+                eprintln!("Day {day} has no registered solution");
+                None
+            }
+        })
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let runs = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            scope.spawn(|| {
+                while let Some(&(day, solution)) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let run = run_day(solution, day, args, quiet, profile);
+                    runs.lock().unwrap().push(run);
+                }
+            });
+        }
+    });
+
+    let mut runs = runs.into_inner().unwrap();
+    runs.sort_by_key(|r| r.day);
+    runs
+}
+
+fn print_answers(answers: &[PartAnswer], recorded: Option<&AnswerFile>) {
+    for answer in answers {
+        let check = recorded.map_or(Check::Unknown, |a| a.check(answer.part, &answer.answer));
+
+        match check {
+            Check::Unknown => println!("Part {}: {}", answer.part, answer.answer),
+            check => println!("Part {}: {} ({check})", answer.part, answer.answer),
+        }
+    }
+}
+
+/// Prints one row per part of every run, marking parts that are unfinished,
+/// over their time limit, wrong or that did not run at all.
+fn print_summary(runs: &[DayRun], args: &RunArgs, limits: &TimeLimits) {
+    let answer_width = runs
+        .iter()
+        .filter_map(|r| r.outcome.as_ref().ok())
+        .flat_map(|(answers, _, _)| answers.iter().map(|a| a.answer.len()))
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:>3}  {:<4}  {:<answer_width$}  {:>9}  Notes",
+        "Day", "Part", "Answer", "Time"
+    );
+
+    let mut total = Duration::ZERO;
+    for run in runs {
+        let (answers, timed) = match &run.outcome {
+            Ok((answers, timed, _)) => (answers, timed),
+            Err(e) => {
+                println!(
+                    "{:>3}  {:<4}  {:<answer_width$}  {:>9}  FAILED: {e}",
+                    run.day, "-", "-", "-"
+                );
+                continue;
+            }
+        };
+        let recorded = args
+            .is_real_input()
+            .then(|| load_answers(run.day))
+            .flatten();
+        let parse_time = timed.time(Stage::Parse).unwrap_or_default();
+        total += parse_time;
+
+        for answer in answers {
+            let solve_time = timed.time(Stage::Solve(answer.part)).unwrap_or_default();
+            total += solve_time;
+
+            let mut notes = Vec::new();
+            if run.unfinished.contains(&answer.part) {
+                notes.push("UNFINISHED".to_string());
+            }
+            if let Some(limit) = limits
+                .for_day(run.day)
+                .filter(|&limit| parse_time + solve_time > limit)
+            {
+                notes.push(format!("OVER BUDGET ({})", format_duration(limit)));
+            }
+            let check = recorded
+                .as_ref()
+                .map_or(Check::Unknown, |a| a.check(answer.part, &answer.answer));
+            if check != Check::Unknown {
+                notes.push(check.to_string());
+            }
+            if let Some(stats) = timed.allocs(Stage::Solve(answer.part)) {
+                notes.push(format!("peak {}", format_bytes(stats.peak)));
+            }
+
+            let row = format!(
+                "{:>3}  {:<4}  {:<answer_width$}  {:>9}  {}",
+                run.day,
+                answer.part.to_string(),
+                answer.answer,
+                format_duration(parse_time + solve_time),
+                notes.join(", ")
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+
+    println!("\nTotal {}", format_duration(total));
+}
+
+/// Runs the selected days. A single day prints its timings and answers as it
+/// goes, several days are run on `jobs` threads and summarized in a table.
+pub fn run(
+    registry: &Registry,
+    args: &RunArgs,
+    report_args: Option<&ReportArgs>,
+    jobs: usize,
+    limits: &TimeLimits,
+) -> Result<(), String> {
+    // Human readable output would end up mixed into a report on stdout
+    let quiet = report_args.is_some_and(|r| r.path.is_none());
+    let profile = args.profile || report_args.is_some();
+    let summary = args.days.len() > 1;
+
+    let runs = if summary {
+        run_days(registry, args, jobs, true, profile)
+    } else {
+        let Some(&day) = args.days.first() else {
+            return Ok(());
+        };
+        let Some(solution) = registry.get(day) else {
+            return Err(format!("Day {day} has no registered solution"));
+        };
+        if !quiet {
+            println!("Day {day}");
+        }
+        let run = run_day(solution, day, args, quiet, profile);
+        match &run.outcome {
+            Err(DayError::Parse(e)) => return Err(e.diagnostic().trim_end().to_string()),
+            Err(DayError::Input(e)) => return Err(e.clone()),
+            Err(e) => return Err(format!("Day {day}: {e}")),
+            Ok(_) => {}
+        }
+        vec![run]
+    };
+
+    // Collecting the metadata runs git, so only when a report was asked for
+    let mut report = report_args.map(|_| Report::new(Metadata::collect()));
+    let mut failed = 0;
+    for run in &runs {
+        let Ok((answers, timed, profile)) = &run.outcome else {
+            failed += 1;
+            continue;
+        };
+        if let Some(report) = &mut report {
+            report.add_day(run.day, answers, timed, profile.as_ref());
+        }
+
+        if quiet {
+            continue;
+        }
+        if let Some(profile) = profile.as_ref().filter(|_| args.profile) {
+            if summary {
+                println!("Day {}", run.day);
+            }
+            print!("{profile}");
+        }
+        if !summary {
+            // Recorded answers only apply to the real input
+            let recorded = args
+                .is_real_input()
+                .then(|| load_answers(run.day))
+                .flatten();
+            print_answers(answers, recorded.as_ref());
+        }
+    }
+    if summary && !quiet {
+        print_summary(&runs, args, limits);
+    }
+
+    if let (Some(report_args), Some(report)) = (report_args, report) {
+        let rendered = report.render(report_args.format);
+        match &report_args.path {
+            Some(path) => fs::write(path, rendered)
+                .map_err(|e| format!("Could not write report to {}: {e}", path.display()))?,
+            None => print!("{rendered}"),
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 day failed".to_string()),
+        n => Err(format!("{n} days failed")),
+    }
+}
+
+/// Benchmarks the selected days and prints their stats, returning the median
+/// time of every stage.
+fn bench_days(registry: &Registry, args: &RunArgs, config: &BenchConfig) -> Baseline {
+    let mut medians = Baseline::default();
+
+    for &day in &args.days {
+        let Some(solution) = registry.get(day) else {
+            eprintln!("Day {day} has no registered solution");
+            continue;
+        };
+        let input = match args.input_path(day).and_then(|path| {
+            fs::read_to_string(&path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+        }) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+
+        println!("Day {day}");
+        let mut harness = Bench::new(config.clone());
+        if args.profile {
+            enable_profiling();
+        }
+        let answers = solution.run(&input, &args.parts, &mut harness);
+        let profile = take_profile();
+        let answers = match answers {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{}", e.with_day(day).diagnostic());
+                continue;
+            }
+        };
+
+        for (stage, stats) in &harness.results {
+            println!("  {:<7} {stats}", stage.to_string());
+            medians.times.insert((day, *stage), stats.median);
+        }
+        for answer in answers {
+            println!("  Part {}: {}", answer.part, answer.answer);
+        }
+        if let Some(profile) = profile {
+            print!("{profile}");
+        }
+    }
+
+    medians
+}
+
+pub fn bench(registry: &Registry, args: &RunArgs, config: &BenchConfig) {
+    bench_days(registry, args, config);
+}
+
+/// Benchmarks the selected days and stores their medians in the baseline at
+/// `path`, keeping the timings of days that were not benchmarked.
+pub fn save_baseline(
+    registry: &Registry,
+    args: &RunArgs,
+    config: &BenchConfig,
+    path: &Path,
+) -> Result<(), String> {
+    let mut saved = Baseline::load(path)?;
+    saved.merge(bench_days(registry, args, config));
+    saved.save(path)?;
+
+    println!("\nSaved baseline to {}", path.display());
+    Ok(())
+}
+
+/// Benchmarks the selected days and compares them with the baseline at `path`.
+/// Returns false if any stage regressed or a part exceeded its time limit.
+pub fn compare_baseline(
+    registry: &Registry,
+    args: &RunArgs,
+    config: &BenchConfig,
+    path: &Path,
+    threshold: f64,
+    limits: &TimeLimits,
+) -> Result<bool, String> {
+    let saved = Baseline::load(path)?;
+    if saved.times.is_empty() {
+        eprintln!("No baseline at {}, only checking limits", path.display());
+    }
+    let current = bench_days(registry, args, config);
+    let comparisons = baseline::compare(&saved, &current, threshold, limits);
+
+    println!(
+        "\n{:>3}  {:<7} {:>9} {:>9} {:>8}",
+        "Day", "Stage", "Baseline", "Current", "Change"
+    );
+    for comparison in &comparisons {
+        println!("{comparison}");
+    }
+
+    let failed = comparisons.iter().filter(|c| c.failed()).count();
+    println!(
+        "\n{failed} of {} stages failed (threshold {:.0}%)",
+        comparisons.len(),
+        threshold * 100.0
+    );
+    Ok(failed == 0)
+}
+
+#[derive(Default)]
+struct VerifySummary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+/// Runs the given days on their real inputs and compares the results with the
+/// recorded answers. Returns false if any part produced a wrong answer.
+pub fn verify(registry: &Registry, days: &[u32], inputs: &InputLocator) -> bool {
+    let mut summary = VerifySummary::default();
+
+    for &day in days {
+        let Some(solution) = registry.get(day) else {
+            continue;
+        };
+
+        let Some(input) = inputs
+            .find(day)
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+        else {
+            println!(
+                "Day {day:>2}:        MISSING (no input at {})",
+                inputs.candidates(day)[0].display()
+            );
+            summary.missing += Part::ALL.len();
+            continue;
+        };
+        let Some(recorded) = load_answers(day) else {
+            summary.failed += Part::ALL.len();
+            continue;
+        };
+
+        let answers = catch(|| solution.run(&input, &Part::ALL, &mut Plain));
+        let answers = match answers {
+            Ok(Ok(answers)) => answers,
+            Ok(Err(e)) => {
+                println!("Day {day:>2}:        FAIL    ({})", e.with_day(day));
+                summary.failed += Part::ALL.len();
+                continue;
+            }
+            Err(e) => {
+                println!("Day {day:>2}:        FAIL    ({e})");
+                summary.failed += Part::ALL.len();
+                continue;
+            }
+        };
+
+        for answer in answers {
+            let check = recorded.check(answer.part, &answer.answer);
+            let status = if check.is_failure() {
+                summary.failed += 1;
+                "FAIL"
+            } else if check == Check::Unknown {
+                summary.missing += 1;
+                "MISSING"
+            } else {
+                summary.passed += 1;
+                "PASS"
+            };
+
+            println!(
+                "Day {day:>2} part {}: {status:<7} {} ({check})",
+                answer.part, answer.answer
+            );
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        summary.passed, summary.failed, summary.missing
+    );
+
+    summary.failed == 0
+}
+
+/// Fuzzes the parsers of `days`, returning whether none of them panicked.
+pub fn fuzz(registry: &Registry, days: &[u32], iterations: u64, seed: u64) -> bool {
+    let mut passed = true;
+    for &day in days {
+        let Some(solution) = registry.get(day) else {
+            continue;
+        };
+        match fuzz::fuzz(day, solution, iterations, &mut Rng::new(seed)) {
+            Ok(()) => println!("Day {day:>2}: PASS    ({iterations} inputs)"),
+            Err(crash) => {
+                passed = false;
+                println!("Day {day:>2}: FAIL    ({crash})");
+            }
+        }
+    }
+    passed
+}
+
+/// Prints a test module for the examples of `day`, or with `check` runs the
+/// day on them and compares with the answers from the description.
+pub fn examples(registry: &Registry, day: u32, dir: &Path, check: bool) -> Result<bool, String> {
+    let path = examples::find(dir, day)?;
+    let puzzle = Puzzle::load(&path)?;
+    if puzzle.examples.is_empty() {
+        return Err(format!("No examples found in {}", path.display()));
+    }
+
+    if !check {
+        print!("{}", puzzle.test_module(None));
+        return Ok(true);
+    }
+
+    let solution = registry
+        .get(day)
+        .ok_or(format!("Day {day} is not implemented"))?;
+    let mut passed = true;
+    for part in Part::ALL {
+        let (Some(example), Some(expected)) = (puzzle.example(part), puzzle.answer(part)) else {
+            println!("Day {day:>2} part {part}: MISSING (no example answer)");
+            continue;
+        };
+
+        let answer = match solution.run(example, &[part], &mut Plain) {
+            Ok(answers) => answers[0].answer.clone(),
+            Err(e) => {
+                passed = false;
+                println!("Day {day:>2} part {part}: FAIL    ({})", e.with_day(day));
+                continue;
+            }
+        };
+        if answer == expected {
+            println!("Day {day:>2} part {part}: PASS    {answer}");
+        } else {
+            passed = false;
+            println!("Day {day:>2} part {part}: FAIL    {answer} (expected {expected})");
+        }
+    }
+    Ok(passed)
+}
+
+/// Runs every implementation of the selected parts on the same input, failing
+/// if they disagree, and prints how long each took compared to the default.
+pub fn compare(registry: &Registry, args: &RunArgs) -> Result<bool, String> {
+    let mut agreed = true;
+    let mut compared = 0;
+
+    for &day in &args.days {
+        for &part in &args.parts {
+            let implementations = registry.implementations(day, part);
+            if implementations.len() < 2 {
+                continue;
+            }
+            compared += 1;
+
+            let path = args.input_path(day)?;
+            let input = fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+
+            println!("Day {day} part {part}");
+            let results: Vec<_> = implementations
+                .iter()
+                .map(|implementation| {
+                    let start = Instant::now();
+                    let answer = catch(|| implementation.run(&input));
+                    let time = start.elapsed();
+                    let answer = match answer {
+                        Ok(Ok(answer)) => Ok(answer),
+                        Ok(Err(e)) => Err(e.with_day(day).to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (implementation.name(), answer, time)
+                })
+                .collect();
+
+            let name_width = results
+                .iter()
+                .map(|(name, _, _)| name.len())
+                .max()
+                .unwrap_or_default();
+            let expected = results[0].1.as_ref().ok().and_then(Option::as_ref);
+            let default_time = results[0].2.as_secs_f64();
+            for (name, answer, time) in &results {
+                let (answer, note) = match answer {
+                    Ok(Some(answer)) if expected.is_none_or(|e| e == answer) => {
+                        (answer.as_str(), "")
+                    }
+                    Ok(Some(answer)) => (answer.as_str(), "  MISMATCH"),
+                    Ok(None) => ("input too large", "  SKIPPED"),
+                    Err(e) => (e.as_str(), "  FAILED"),
+                };
+                if note == "  MISMATCH" || note == "  FAILED" {
+                    agreed = false;
+                }
+                // The default can finish below the resolution of the clock
+                let ratio = if default_time > 0.0 && note != "  SKIPPED" {
+                    format!("{:.2}x", time.as_secs_f64() / default_time)
+                } else {
+                    "-".to_string()
+                };
+                println!(
+                    "  {name:<name_width$}  {:>9}  {ratio:>8}  {answer}{note}",
+                    format_duration(*time)
+                );
+            }
+        }
+    }
+
+    if compared == 0 {
+        return Err("None of the selected parts has alternate implementations".to_string());
+    }
+    Ok(agreed)
+}
+
+pub fn record_answer(day: u32, part: Part, record: Record) -> Result<(), String> {
+    let mut answers = AnswerFile::load(Path::new(ANSWERS_DIR), day)?;
+    answers.record(part, record);
+    answers.save()?;
+
+    println!(
+        "Recorded in {}",
+        AnswerFile::path(Path::new(ANSWERS_DIR), day).display()
+    );
+    Ok(())
+}
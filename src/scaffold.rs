@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::Path;
+
+use aoc25::examples::{self, Puzzle};
+
+/// Last day of an Advent of Code calendar
+pub const MAX_DAY: u32 = 25;
+
+const SRC_DIR: &str = "src";
+
+fn template(day: u32) -> String {
+    format!(
+        r#"use crate::parse::{{ParseError, parse_number}};
+use crate::solution::{{Part, Solution}};
+
+type Input = Vec<i64>;
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {{
+    input.lines().map(|l| parse_number(input, l)).collect()
+}}
+
+pub fn part1(_input: &Input) -> i64 {{
+    0
+}}
+
+pub fn part2(_input: &Input) -> i64 {{
+    0
+}}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    type Input<'a> = Input;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const UNFINISHED: &'static [Part] = &[Part::One, Part::Two];
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError> {{
+        parse_input(input)
+    }}
+
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1 {{
+        part1(input)
+    }}
+
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2 {{
+        part2(input)
+    }}
+}}
+
+"#
+    )
+}
+
+/// Test module with an empty example, for when no puzzle description is saved.
+fn placeholder_tests(day: u32) -> String {
+    format!(
+        r#"#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // Paste the example from the puzzle description, or save the description
+    // and print the tests with `aoc25 examples {day}`
+    const TEST_INPUT: &str = "";
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn test_p1() {{
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(0, part1(&input));
+    }}
+
+    #[test]
+    #[ignore = "example not filled in yet"]
+    fn test_p2() {{
+        let input = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(0, part2(&input));
+    }}
+}}
+"#
+    )
+}
+
+/// Inserts `line` among the lines that have a sort key, before the first one
+/// with a larger key. Fails if there is no such line or one has the same key.
+fn insert_sorted<K: Ord>(
+    source: &str,
+    line: &str,
+    key: impl Fn(&str) -> Option<K>,
+) -> Result<String, String> {
+    let line_key = key(line).expect("Inserted line has no key");
+    let lines: Vec<&str> = source.lines().collect();
+    let keyed: Vec<(usize, K)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, key(l)?)))
+        .collect();
+
+    let Some((last, _)) = keyed.last() else {
+        return Err(format!("Found nowhere to add `{}`", line.trim()));
+    };
+    if keyed.iter().any(|(_, k)| *k == line_key) {
+        return Err(format!("Found an existing `{}`", line.trim()));
+    }
+    let at = keyed
+        .iter()
+        .find(|(_, k)| *k > line_key)
+        .map_or(last + 1, |(i, _)| *i);
+
+    let mut result = lines[..at].to_vec();
+    result.push(line);
+    result.extend(&lines[at..]);
+    Ok(result.join("\n") + "\n")
+}
+
+/// Declares the module in `lib.rs`, where rustfmt keeps `pub mod` sorted as
+/// strings (`day10` before `day2`).
+fn add_module(lib: &str, day: u32) -> Result<String, String> {
+    insert_sorted(lib, &format!("pub mod day{day};"), |l| {
+        l.strip_prefix("pub mod ")?
+            .strip_suffix(';')
+            .map(str::to_string)
+    })
+}
+
+/// Imports and registers the day in `registry.rs`, both ordered by day.
+fn add_registration(registry: &str, day: u32) -> Result<String, String> {
+    let registry = insert_sorted(registry, &format!("use crate::day{day}::Day{day};"), |l| {
+        let (day, _) = l.strip_prefix("use crate::day")?.split_once("::")?;
+        day.parse::<u32>().ok()
+    })?;
+
+    insert_sorted(
+        &registry,
+        &format!("    registry.register({day}, Day{day});"),
+        |l| {
+            let (day, _) = l
+                .trim_start()
+                .strip_prefix("registry.register(")?
+                .split_once(',')?;
+            day.parse::<u32>().ok()
+        },
+    )
+}
+
+/// Creates `src/day<N>.rs` from the template and registers it. The tests use
+/// the examples of a saved puzzle description in `puzzle_dir` if there is one.
+pub fn new_day(day: u32, puzzle_dir: &Path) -> Result<(), String> {
+    let src = Path::new(SRC_DIR);
+    let lib_path = src.join("lib.rs");
+    let registry_path = src.join("registry.rs");
+    let day_path = src.join(format!("day{day}.rs"));
+
+    if !lib_path.is_file() || !registry_path.is_file() {
+        return Err(format!(
+            "No {} or {}, run this from the repository root",
+            lib_path.display(),
+            registry_path.display()
+        ));
+    }
+    if day_path.exists() {
+        return Err(format!(
+            "{} already exists, not overwriting it",
+            day_path.display()
+        ));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()))
+    };
+    let lib = add_module(&read(&lib_path)?, day)?;
+    let registry = add_registration(&read(&registry_path)?, day)?;
+
+    let tests = match examples::find(puzzle_dir, day) {
+        // The template answers 0, so the examples fail until the day is solved
+        Ok(path) => Puzzle::load(&path)?.test_module(Some("not solved yet")),
+        Err(_) => placeholder_tests(day),
+    };
+
+    let write = |path: &Path, content: &str| {
+        fs::write(path, content).map_err(|e| format!("Could not write {}: {e}", path.display()))
+    };
+    write(&day_path, &(template(day) + &tests))?;
+    write(&lib_path, &lib)?;
+    write(&registry_path, &registry)?;
+    println!("Created {}", day_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "use crate::day1::Day1;
+use crate::day2::Day2;
+use crate::day10::Day10;
+use crate::solution::Day;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+
+    registry.register(1, Day1::default());
+    registry.register(2, Day2);
+    registry.register(10, Day10);
+
+    registry
+}
+";
+
+    #[test]
+    fn test_add_module() {
+        let lib = "//! Docs\n\npub mod answers;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod util;\n";
+        assert_eq!(
+            "//! Docs\n\npub mod answers;\npub mod day1;\npub mod day10;\npub mod day13;\npub mod day2;\npub mod util;\n",
+            add_module(lib, 13).unwrap()
+        );
+        assert!(add_module(lib, 2).is_err());
+    }
+
+    #[test]
+    fn test_add_registration() {
+        let registry = add_registration(REGISTRY, 3).unwrap();
+        assert!(
+            registry.contains("use crate::day2::Day2;\nuse crate::day3::Day3;\nuse crate::day10")
+        );
+        assert!(registry.contains(
+            "registry.register(2, Day2);\n    registry.register(3, Day3);\n    registry.register(10"
+        ));
+
+        let registry = add_registration(REGISTRY, 11).unwrap();
+        assert!(registry.contains("use crate::day11::Day11;\nuse crate::solution::Day;"));
+        assert!(registry.contains("registry.register(11, Day11);\n\n    registry\n"));
+
+        assert!(add_registration(REGISTRY, 10).is_err());
+    }
+
+    #[test]
+    fn test_template_uses_day() {
+        let source = template(13) + &placeholder_tests(13);
+        assert!(source.contains("impl Solution for Day13 {"));
+        assert!(source.contains("`aoc25 examples 13`"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::parse::ParseError;
+use crate::timed::in_span;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part `{s}`, expected 1 or 2")),
+        }
+    }
+}
+
+/// A single day's puzzle. Any per-day configuration (such as the dial start
+/// position on day 1) lives in the implementing struct.
+pub trait Solution {
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    /// Parts that do not compute a real answer yet.
+    const UNFINISHED: &'static [Part] = &[];
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>, ParseError>;
+    fn part1(&self, input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(&self, input: &Self::Input<'_>) -> Self::Part2;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "Parse"),
+            Stage::Solve(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// Wraps each stage of a run, e.g. to time it. Implementations must call `f`
+/// at least once, the result of the last call is the one that is kept.
+pub trait Harness {
+    fn stage(&mut self, stage: Stage, f: &mut dyn FnMut());
+}
+
+/// Runs every stage exactly once without measuring anything.
+pub struct Plain;
+
+impl Harness for Plain {
+    fn stage(&mut self, _stage: Stage, f: &mut dyn FnMut()) {
+        f();
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartAnswer {
+    pub part: Part,
+    pub answer: String,
+}
+
+/// Object safe view of a `Solution`, used to store days in the registry.
+pub trait Day: Send + Sync {
+    fn run(
+        &self,
+        input: &str,
+        parts: &[Part],
+        harness: &mut dyn Harness,
+    ) -> Result<Vec<PartAnswer>, ParseError>;
+    fn unfinished(&self) -> &'static [Part];
+}
+
+fn run_stage<T>(harness: &mut dyn Harness, stage: Stage, mut f: impl FnMut() -> T) -> T {
+    let name = stage.to_string();
+    let mut result = None;
+    harness.stage(stage, &mut || result = Some(in_span(&name, &mut f)));
+    result.expect("Harness did not run the stage")
+}
+
+impl<S: Solution + Send + Sync> Day for S {
+    fn run(
+        &self,
+        input: &str,
+        parts: &[Part],
+        harness: &mut dyn Harness,
+    ) -> Result<Vec<PartAnswer>, ParseError> {
+        let parsed = run_stage(harness, Stage::Parse, || self.parse(input))?;
+
+        Ok(parts
+            .iter()
+            .map(|&part| {
+                let stage = Stage::Solve(part);
+                let answer = match part {
+                    Part::One => run_stage(harness, stage, || self.part1(&parsed)).to_string(),
+                    Part::Two => run_stage(harness, stage, || self.part2(&parsed)).to_string(),
+                };
+                PartAnswer { part, answer }
+            })
+            .collect())
+    }
+
+    fn unfinished(&self) -> &'static [Part] {
+        S::UNFINISHED
+    }
+}
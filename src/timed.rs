@@ -1,28 +1,643 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::marker::PhantomData;
 use std::time::{Duration, Instant};
 
-pub fn print_timespan(title: &str, time: Duration) {
+use crate::alloc::{self, AllocStats};
+use crate::solution::{Harness, Stage};
+
+fn format_timespan(title: &str, time: Duration) -> String {
     let millis = time.subsec_millis();
     let micros = time.subsec_micros() - millis * 1000;
     let nanos = time.subsec_nanos() - millis * 1000000 - micros * 1000;
-    println!(
+    format!(
         "{title} took {}s {}ms {}us {}ns",
         time.as_secs(),
         millis,
         micros,
         nanos
-    );
+    )
 }
-pub fn timed<F, T>(f: F) -> T
+
+pub fn print_timespan(title: &str, time: Duration) {
+    println!("{}", format_timespan(title, time));
+}
+pub fn timed<F, T>(mut f: F) -> T
 where
-    F: Fn() -> T,
+    F: FnMut() -> T,
 {
     let start = Instant::now();
 
     let result = f();
 
     let end = Instant::now();
-    
+
     print_timespan("Execution", end - start);
 
     result
 }
+
+/// Harness that times every stage once, optionally printing how long it took.
+#[derive(Default)]
+pub struct Timed {
+    pub quiet: bool,
+    pub times: Vec<(Stage, Duration)>,
+    /// Only recorded with the counting allocator
+    pub allocs: Vec<(Stage, AllocStats)>,
+}
+
+impl Timed {
+    pub fn time(&self, stage: Stage) -> Option<Duration> {
+        self.times
+            .iter()
+            .find(|(s, _)| *s == stage)
+            .map(|&(_, time)| time)
+    }
+
+    pub fn allocs(&self, stage: Stage) -> Option<AllocStats> {
+        self.allocs
+            .iter()
+            .find(|(s, _)| *s == stage)
+            .map(|&(_, stats)| stats)
+    }
+}
+
+impl Harness for Timed {
+    fn stage(&mut self, stage: Stage, f: &mut dyn FnMut()) {
+        let start = Instant::now();
+        let ((), stats) = alloc::measure(f);
+        let time = start.elapsed();
+
+        let stats = alloc::enabled().then_some(stats);
+        if !self.quiet {
+            let timespan = format_timespan(&stage.to_string(), time);
+            match stats {
+                Some(stats) => println!("{timespan} ({stats})"),
+                None => println!("{timespan}"),
+            }
+        }
+        self.times.push((stage, time));
+        self.allocs.extend(stats.map(|stats| (stage, stats)));
+    }
+}
+
+/// Formats a duration with three significant digits in the largest fitting unit.
+pub fn format_duration(time: Duration) -> String {
+    let nanos = time.as_nanos() as f64;
+    let (value, unit) = if nanos >= 1e9 {
+        (nanos / 1e9, "s")
+    } else if nanos >= 1e6 {
+        (nanos / 1e6, "ms")
+    } else if nanos >= 1e3 {
+        (nanos / 1e3, "us")
+    } else {
+        (nanos, "ns")
+    };
+
+    let decimals = if unit == "ns" || value >= 100.0 {
+        0
+    } else if value >= 10.0 {
+        1
+    } else {
+        2
+    };
+    format!("{value:.decimals$}{unit}")
+}
+
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or(format!("Duration `{s}` has no unit"))?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("Invalid duration `{s}`"))?;
+
+    let seconds = match unit {
+        "s" => value,
+        "ms" => value / 1e3,
+        "us" => value / 1e6,
+        "ns" => value / 1e9,
+        _ => {
+            return Err(format!(
+                "Unknown duration unit `{unit}`, expected s, ms, us or ns"
+            ));
+        }
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("Invalid duration `{s}`"))
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct BenchConfig {
+    /// How long to run the function before sampling starts
+    pub warmup: Duration,
+    /// How long to spend collecting samples
+    pub budget: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(200),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+/// Aim for samples of at least this length so the timer resolution does not
+/// dominate the measurement of very fast functions.
+const MIN_SAMPLE_TIME: Duration = Duration::from_micros(100);
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct BenchStats {
+    pub samples: usize,
+    /// The number of times the function was called for every sample
+    pub iterations: u64,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    /// Samples outside 1.5 times the interquartile range (Tukey's fences)
+    pub outliers: usize,
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl BenchStats {
+    /// Computes statistics from per-iteration sample times in nanoseconds.
+    pub fn from_samples(samples: &[f64], iterations: u64) -> Self {
+        assert!(!samples.is_empty(), "No samples to compute statistics from");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low_fence, high_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let outliers = sorted
+            .iter()
+            .filter(|&&s| s < low_fence || s > high_fence)
+            .count();
+
+        let nanos = |v: f64| Duration::from_nanos(v.round() as u64);
+        Self {
+            samples: sorted.len(),
+            iterations,
+            min: nanos(sorted[0]),
+            median: nanos(percentile(&sorted, 0.5)),
+            mean: nanos(mean),
+            stddev: nanos(variance.sqrt()),
+            p95: nanos(percentile(&sorted, 0.95)),
+            outliers,
+        }
+    }
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {}  median {}  mean {} ± {}  p95 {}  ({} samples x {}, {} outliers)",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.stddev),
+            format_duration(self.p95),
+            self.samples,
+            self.iterations,
+            self.outliers
+        )
+    }
+}
+
+/// Benchmarks `f` by first warming up and then repeatedly sampling it until
+/// the time budget is spent.
+pub fn bench<F, T>(config: &BenchConfig, mut f: F) -> BenchStats
+where
+    F: FnMut() -> T,
+{
+    let warmup_start = Instant::now();
+    let mut warmup_runs = 0u32;
+    while warmup_runs == 0 || warmup_start.elapsed() < config.warmup {
+        black_box(f());
+        warmup_runs += 1;
+    }
+    let per_run = warmup_start.elapsed() / warmup_runs;
+
+    let iterations = if per_run.is_zero() {
+        MIN_SAMPLE_TIME.as_nanos() as u64
+    } else {
+        MIN_SAMPLE_TIME
+            .as_nanos()
+            .div_ceil(per_run.as_nanos())
+            .max(1) as u64
+    };
+
+    let mut samples = Vec::new();
+    let sampling_start = Instant::now();
+    while samples.len() < config.min_samples
+        || (samples.len() < config.max_samples && sampling_start.elapsed() < config.budget)
+    {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(f());
+        }
+        samples.push(start.elapsed().as_nanos() as f64 / iterations as f64);
+    }
+
+    BenchStats::from_samples(&samples, iterations)
+}
+
+/// Harness that benchmarks every stage and keeps the statistics.
+pub struct Bench {
+    pub config: BenchConfig,
+    pub results: Vec<(Stage, BenchStats)>,
+}
+
+impl Bench {
+    pub fn new(config: BenchConfig) -> Self {
+        Self {
+            config,
+            results: Vec::new(),
+        }
+    }
+}
+
+impl Harness for Bench {
+    fn stage(&mut self, stage: Stage, f: &mut dyn FnMut()) {
+        let stats = bench(&self.config, f);
+        self.results.push((stage, stats));
+    }
+}
+
+struct SpanNode {
+    name: String,
+    children: Vec<usize>,
+    total: Duration,
+    calls: u64,
+}
+
+/// Collects the span tree for the current thread. The root node is an unnamed
+/// span that is never closed.
+struct Profiler {
+    nodes: Vec<SpanNode>,
+    /// Open spans as node, start and id, the root first
+    stack: Vec<(usize, Instant, u64)>,
+}
+
+impl Profiler {
+    fn new() -> Self {
+        Self {
+            nodes: vec![SpanNode {
+                name: String::new(),
+                children: Vec::new(),
+                total: Duration::ZERO,
+                calls: 0,
+            }],
+            stack: vec![(0, Instant::now(), 0)],
+        }
+    }
+
+    /// Opens a span and returns its id.
+    fn enter(&mut self, name: &str) -> u64 {
+        let (parent, _, _) = *self.stack.last().unwrap();
+        let existing = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&c| self.nodes[c].name == name);
+
+        let node = existing.unwrap_or_else(|| {
+            self.nodes.push(SpanNode {
+                name: name.to_string(),
+                children: Vec::new(),
+                total: Duration::ZERO,
+                calls: 0,
+            });
+            let node = self.nodes.len() - 1;
+            self.nodes[parent].children.push(node);
+            node
+        });
+
+        let id = NEXT_SPAN_ID.get();
+        NEXT_SPAN_ID.set(id + 1);
+        self.stack.push((node, Instant::now(), id));
+        id
+    }
+
+    /// Closes the span `id`, along with any span opened inside it that is
+    /// still open. Does nothing if that span was closed already.
+    fn exit(&mut self, id: u64) {
+        let Some(depth) = self.stack.iter().rposition(|&(_, _, i)| i == id) else {
+            return;
+        };
+        while self.stack.len() > depth {
+            let (node, start, _) = self.stack.pop().unwrap();
+            self.nodes[node].total += start.elapsed();
+            self.nodes[node].calls += 1;
+        }
+    }
+
+    fn collect(&self, node: usize, depth: usize, spans: &mut Vec<SpanStats>) {
+        let n = &self.nodes[node];
+        let child_total: Duration = n.children.iter().map(|&c| self.nodes[c].total).sum();
+        spans.push(SpanStats {
+            name: n.name.clone(),
+            depth,
+            total: n.total,
+            self_time: n.total.saturating_sub(child_total),
+            calls: n.calls,
+        });
+
+        for &child in &n.children {
+            self.collect(child, depth + 1, spans);
+        }
+    }
+
+    fn into_profile(self) -> Profile {
+        let mut spans = Vec::new();
+        for &child in &self.nodes[0].children {
+            self.collect(child, 0, &mut spans);
+        }
+        Profile { spans }
+    }
+}
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+    // Not reset with the profiler, so that guards from before cannot close
+    // the spans of a new one
+    static NEXT_SPAN_ID: Cell<u64> = const { Cell::new(1) };
+}
+
+/// Starts collecting spans on the current thread, discarding anything that
+/// was collected before.
+pub fn enable_profiling() {
+    PROFILER.with_borrow_mut(|p| *p = Some(Profiler::new()));
+}
+
+/// Stops collecting spans on the current thread and returns what was
+/// collected, or `None` if profiling was not enabled.
+pub fn take_profile() -> Option<Profile> {
+    PROFILER
+        .with_borrow_mut(|p| p.take())
+        .map(Profiler::into_profile)
+}
+
+/// Closes its span when dropped. Does nothing if profiling was off when the
+/// span was opened.
+#[must_use = "the span is closed when the guard is dropped"]
+pub struct SpanGuard {
+    /// Id of the span, `None` if profiling was off
+    id: Option<u64>,
+    // Spans are tracked per thread, so the guard has to stay on this one
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            PROFILER.with_borrow_mut(|p| {
+                if let Some(p) = p {
+                    p.exit(id);
+                }
+            });
+        }
+    }
+}
+
+/// Opens a named span nested in the currently open span. Spans with the same
+/// name and parent are merged.
+pub fn span(name: &str) -> SpanGuard {
+    let id = PROFILER.with_borrow_mut(|p| p.as_mut().map(|p| p.enter(name)));
+
+    SpanGuard {
+        id,
+        _not_send: PhantomData,
+    }
+}
+
+pub fn in_span<F, T>(name: &str, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let _span = span(name);
+    f()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpanStats {
+    pub name: String,
+    /// Nesting level, top level spans are at depth 0
+    pub depth: usize,
+    pub total: Duration,
+    /// Time not spent in any child span
+    pub self_time: Duration,
+    pub calls: u64,
+}
+
+/// Flattened span tree, every span is directly followed by its children.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Profile {
+    pub spans: Vec<SpanStats>,
+}
+
+impl Profile {
+    /// Returns the spans nested in the top level span `name`, as if they were
+    /// top level spans themselves.
+    pub fn subtree(&self, name: &str) -> Profile {
+        let spans = self
+            .spans
+            .iter()
+            .skip_while(|s| s.depth != 0 || s.name != name)
+            .skip(1)
+            .take_while(|s| s.depth > 0)
+            .map(|s| SpanStats {
+                depth: s.depth - 1,
+                ..s.clone()
+            })
+            .collect();
+
+        Profile { spans }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name_width = self
+            .spans
+            .iter()
+            .map(|s| s.depth * 2 + s.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        writeln!(
+            f,
+            "{:<name_width$}  {:>9}  {:>9}  {:>7}",
+            "Span", "Total", "Self", "Calls"
+        )?;
+        for s in &self.spans {
+            let name = format!("{}{}", "  ".repeat(s.depth), s.name);
+            writeln!(
+                f,
+                "{name:<name_width$}  {:>9}  {:>9}  {:>7}",
+                format_duration(s.total),
+                format_duration(s.self_time),
+                s.calls
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5.0, 1.0, 3.0, 2.0, 4.0, 100.0];
+        let stats = BenchStats::from_samples(&samples, 1);
+
+        assert_eq!(6, stats.samples);
+        assert_eq!(Duration::from_nanos(1), stats.min);
+        assert_eq!(Duration::from_nanos(3), stats.median);
+        assert_eq!(Duration::from_nanos(19), stats.mean);
+        assert_eq!(Duration::from_nanos(100), stats.p95);
+        assert_eq!(1, stats.outliers);
+    }
+
+    #[test]
+    fn test_bench() {
+        let config = BenchConfig {
+            warmup: Duration::from_millis(1),
+            budget: Duration::from_millis(5),
+            min_samples: 3,
+            max_samples: 50,
+        };
+        let mut calls = 0;
+        let stats = bench(&config, || calls += 1);
+
+        assert!(stats.samples >= 3 && stats.samples <= 50);
+        assert!(stats.min <= stats.median && stats.median <= stats.p95);
+        assert!(calls as u64 >= stats.samples as u64 * stats.iterations);
+    }
+
+    #[test]
+    fn test_spans() {
+        enable_profiling();
+        for _ in 0..3 {
+            let _outer = span("Outer");
+            in_span("Inner", || std::thread::sleep(Duration::from_millis(1)));
+            let _other = span("Other");
+        }
+        let profile = take_profile().unwrap();
+
+        let names: Vec<_> = profile
+            .spans
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth, s.calls))
+            .collect();
+        assert_eq!(
+            vec![("Outer", 0, 3), ("Inner", 1, 3), ("Other", 1, 3)],
+            names
+        );
+
+        let inner = profile.subtree("Outer");
+        assert_eq!(2, inner.spans.len());
+        assert_eq!(0, inner.spans[0].depth);
+        assert!(profile.subtree("Inner").spans.is_empty());
+
+        let outer = &profile.spans[0];
+        assert!(outer.total >= Duration::from_millis(3));
+        assert!(outer.self_time < outer.total);
+    }
+
+    #[test]
+    fn test_spans_dropped_out_of_order() {
+        enable_profiling();
+        let outer = span("Outer");
+        let inner = span("Inner");
+        // Closes the inner span too, which must not close anything again
+        drop(outer);
+        let next = span("Next");
+        drop(inner);
+        drop(next);
+        let profile = take_profile().unwrap();
+
+        let names: Vec<_> = profile
+            .spans
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth, s.calls))
+            .collect();
+        assert_eq!(
+            vec![("Outer", 0, 1), ("Inner", 1, 1), ("Next", 0, 1)],
+            names
+        );
+    }
+
+    #[test]
+    fn test_stale_guard_keeps_later_spans_open() {
+        enable_profiling();
+        let old = span("Old");
+        // Discards the open span, its guard must not close any new one
+        enable_profiling();
+        let outer = span("Outer");
+        let inner = span("Inner");
+        drop(outer);
+
+        // Open at the same depths as the closed spans
+        let a = span("A");
+        let b = span("B");
+        drop(inner);
+        drop(old);
+        std::thread::sleep(Duration::from_millis(2));
+        drop(b);
+        drop(a);
+        let profile = take_profile().unwrap();
+
+        let names: Vec<_> = profile
+            .spans
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth, s.calls))
+            .collect();
+        assert_eq!(
+            vec![("Outer", 0, 1), ("Inner", 1, 1), ("A", 0, 1), ("B", 1, 1)],
+            names
+        );
+        assert!(profile.spans[3].total >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_spans_disabled() {
+        take_profile();
+        in_span("Ignored", || {});
+        assert_eq!(None, take_profile());
+    }
+
+    #[test]
+    fn test_durations() {
+        assert_eq!(Duration::from_millis(1500), parse_duration("1.5s").unwrap());
+        assert_eq!(Duration::from_micros(250), parse_duration("250us").unwrap());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("10m").is_err());
+        assert!(parse_duration("99999999999999999999999s").is_err());
+        assert_eq!("9.62ms", format_duration(Duration::from_micros(9616)));
+        assert_eq!("231ms", format_duration(Duration::from_millis(231)));
+    }
+}
@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 
 pub type GaussEliminationResult = Vec<Vec<f64>>;
 
-fn shape(m: &Vec<Vec<f64>>) -> (usize, usize) {
+fn shape(m: &[Vec<f64>]) -> (usize, usize) {
     let height = m.len();
     assert_ne!(height, 0);
     let width = m[0].len();
@@ -10,7 +10,7 @@ fn shape(m: &Vec<Vec<f64>>) -> (usize, usize) {
     (width, height)
 }
 
-fn cmp_vec(a: &Vec<f64>, b: &Vec<f64>) -> Ordering {
+fn cmp_vec(a: &[f64], b: &[f64]) -> Ordering {
     let len = a.len().min(b.len());
     for i in 0..len {
         let c = a[i].abs().total_cmp(&b[i].abs());
@@ -22,7 +22,7 @@ fn cmp_vec(a: &Vec<f64>, b: &Vec<f64>) -> Ordering {
 }
 
 fn make_stair_shape(m: &mut Vec<Vec<f64>>) -> Vec<Vec<f64>> {
-    let (width, height) = shape(m);
+    let (width, _height) = shape(m);
 
     let mut res = Vec::new();
 
@@ -40,7 +40,7 @@ fn make_stair_shape(m: &mut Vec<Vec<f64>>) -> Vec<Vec<f64>> {
             res.push(pivot_value.clone());
             m.remove(pivot_index);
 
-            for (j, row_value) in m.iter_mut().enumerate() {
+            for row_value in m.iter_mut() {
                 // Zero in this position means nothing to remove
                 if row_value[i] == 0.0 {
                     continue;
@@ -58,17 +58,17 @@ fn make_stair_shape(m: &mut Vec<Vec<f64>>) -> Vec<Vec<f64>> {
     res
 }
 
-pub fn gauss_elimination(m: &Vec<Vec<f64>>) -> GaussEliminationResult {
-    let mut m = m.clone();
-    let (width, height) = shape(&m);
+pub fn gauss_elimination(m: &[Vec<f64>]) -> GaussEliminationResult {
+    let mut m = m.to_vec();
+    let (width, _height) = shape(&m);
 
     let mut m = make_stair_shape(&mut m);
 
-    m.sort_unstable_by(cmp_vec);
+    m.sort_unstable_by(|a, b| cmp_vec(a, b));
 
     let mut res = vec![vec![None; width - 1]];
-    for (row_idx, row) in m.iter().enumerate() {
-        let first_col = row.iter().enumerate().find(|&(i, p)| *p != 0.0);
+    for row in m.iter() {
+        let first_col = row.iter().enumerate().find(|&(_i, p)| *p != 0.0);
         if first_col.is_none() {
             continue;
         }
@@ -78,7 +78,7 @@ pub fn gauss_elimination(m: &Vec<Vec<f64>>) -> GaussEliminationResult {
         value[0] = row[width - 1];
 
         for col_idx in ((first_col_idx + 1)..(width - 1)).rev() {
-            let mut v = row[col_idx];
+            let v = row[col_idx];
             if v == 0.0 {
                 continue;
             }
@@ -114,7 +114,7 @@ pub fn gauss_elimination(m: &Vec<Vec<f64>>) -> GaussEliminationResult {
         .collect()
 }
 
-pub fn equation_system_i64_to_f64(m: &Vec<Vec<i64>>) -> Vec<Vec<f64>> {
+pub fn equation_system_i64_to_f64(m: &[Vec<i64>]) -> Vec<Vec<f64>> {
     m.iter()
         .map(|r| r.iter().map(|&v| v as f64).collect())
         .collect()
@@ -137,7 +137,7 @@ mod tests {
 
         assert_eq!(
             r,
-            equation_system_i64_to_f64(&vec![
+            equation_system_i64_to_f64(&[
                 vec![2, 5, 1, 0, 3, 0],
                 vec![1, -1, 1, 0, -1, 1],
                 vec![0, -1, -1, 1, 0, 0]
@@ -206,7 +206,7 @@ mod tests {
 
         assert_eq!(
             r,
-            equation_system_i64_to_f64(&vec![
+            equation_system_i64_to_f64(&[
                 vec![2, 5, 1, 0, 3, 0],
                 vec![1, -1, 1, 0, -1, 1],
                 vec![0, -1, -1, 1, 0, 0]
@@ -239,7 +239,7 @@ mod tests {
 
         assert_eq!(
             r,
-            equation_system_i64_to_f64(&vec![vec![5, 9, 13, 20, 1, 0, 3, 19]])
+            equation_system_i64_to_f64(&[vec![5, 9, 13, 20, 1, 0, 3, 19]])
         );
     }
 }
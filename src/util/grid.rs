@@ -1,8 +1,32 @@
-use std::io::Lines;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use crate::parse::ParseError;
 use crate::util::vec2::Vec2;
 
-#[derive(Clone)]
+/// A cell that is drawn as a single character, and parsed back from it.
+pub trait GridChar: Sized {
+    fn to_char(&self) -> char;
+
+    /// `None` if `c` is not a cell.
+    fn from_char(c: char) -> Option<Self>;
+}
+
+impl GridChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Grid<T> {
     pub cells: Vec<T>,
     pub width: usize,
@@ -19,6 +43,23 @@ impl<T: Clone> Grid<T> {
     }
 }
 
+impl<T> Grid<T> {
+    /// A grid with the value of every cell from `f(x, y)`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
 impl<T: Clone> Grid<T> {
     pub fn from_lines<'a, TL: Fn(char) -> T>(
         lines: impl Iterator<Item = &'a str>,
@@ -52,7 +93,7 @@ impl<T: Clone> Grid<T> {
     }
 }
 
-static NEIGHBOUR_OFFSETS: [Vec2<i64>; 8] = [
+static MOORE_OFFSETS: [Vec2<i64>; 8] = [
     Vec2 { x: -1, y: 0 },
     Vec2 { x: 1, y: 0 },
     Vec2 { x: -1, y: 1 },
@@ -63,6 +104,76 @@ static NEIGHBOUR_OFFSETS: [Vec2<i64>; 8] = [
     Vec2 { x: 1, y: -1 },
 ];
 
+static VON_NEUMANN_OFFSETS: [Vec2<i64>; 4] = [
+    Vec2 { x: -1, y: 0 },
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 0, y: 1 },
+    Vec2 { x: 0, y: -1 },
+];
+
+static DIAGONAL_OFFSETS: [Vec2<i64>; 4] = [
+    Vec2 { x: -1, y: 1 },
+    Vec2 { x: 1, y: 1 },
+    Vec2 { x: -1, y: -1 },
+    Vec2 { x: 1, y: -1 },
+];
+
+pub static KNIGHT_OFFSETS: [Vec2<i64>; 8] = [
+    Vec2 { x: 1, y: 2 },
+    Vec2 { x: 2, y: 1 },
+    Vec2 { x: 2, y: -1 },
+    Vec2 { x: 1, y: -2 },
+    Vec2 { x: -1, y: -2 },
+    Vec2 { x: -2, y: -1 },
+    Vec2 { x: -2, y: 1 },
+    Vec2 { x: -1, y: 2 },
+];
+
+/// The cells around a cell, as offsets from it
+#[derive(Clone, Copy, Debug)]
+pub enum Neighbourhood<'a> {
+    /// The four orthogonal neighbours
+    VonNeumann,
+    /// All eight neighbours
+    Moore,
+    /// The four diagonal neighbours
+    Diagonal,
+    /// Any offsets, e.g. [`KNIGHT_OFFSETS`] or from [`within_radius`]
+    Custom(&'a [Vec2<i64>]),
+}
+
+impl<'a> Neighbourhood<'a> {
+    pub fn offsets(self) -> &'a [Vec2<i64>] {
+        match self {
+            Neighbourhood::VonNeumann => &VON_NEUMANN_OFFSETS,
+            Neighbourhood::Moore => &MOORE_OFFSETS,
+            Neighbourhood::Diagonal => &DIAGONAL_OFFSETS,
+            Neighbourhood::Custom(offsets) => offsets,
+        }
+    }
+}
+
+/// Offsets of up to `radius` steps away, orthogonal steps only for the von
+/// Neumann neighbourhood and diagonal ones as well for Moore. The cell itself
+/// is not included.
+pub fn within_radius(radius: i64, diagonal: bool) -> Vec<Vec2<i64>> {
+    let mut offsets = Vec::new();
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            let offset = Vec2::new(x, y);
+            let distance = if diagonal {
+                x.abs().max(y.abs())
+            } else {
+                offset.manhattan_distance()
+            };
+            if distance != 0 && distance <= radius {
+                offsets.push(offset);
+            }
+        }
+    }
+    offsets
+}
+
 impl<T: Clone> Grid<T> {
     pub fn at(&self, x: i64, y: i64) -> Option<T> {
         if x < 0 || x >= self.width as i64 || y < 0 || y >= self.height as i64 {
@@ -72,13 +183,46 @@ impl<T: Clone> Grid<T> {
         }
     }
 
+    /// Wraps around the edges, as if the grid was a torus. `None` only if
+    /// the grid is empty.
+    pub fn at_wrapping(&self, x: i64, y: i64) -> Option<T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as i64);
+        let y = y.rem_euclid(self.height as i64);
+        Some(self.cells[(x + y * self.width as i64) as usize].clone())
+    }
+
+    /// The Moore neighbours inside the grid.
     pub fn neighbours(&self, x: i64, y: i64) -> impl Iterator<Item = (Vec2<i64>, T)> {
-        NEIGHBOUR_OFFSETS
+        self.neighbours_in(x, y, Neighbourhood::Moore)
+    }
+
+    /// The neighbours inside the grid, with their offsets.
+    pub fn neighbours_in<'a>(
+        &'a self,
+        x: i64,
+        y: i64,
+        neighbourhood: Neighbourhood<'a>,
+    ) -> impl Iterator<Item = (Vec2<i64>, T)> + 'a {
+        neighbourhood
+            .offsets()
             .iter()
-            .filter_map(move |n| match self.at(n.x + x, n.y + y) {
-                Some(v) => Some((n.clone(), v)),
-                None => None,
-            })
+            .filter_map(move |n| self.at(n.x + x, n.y + y).map(|v| (*n, v)))
+    }
+
+    /// Every neighbour, wrapping around the edges.
+    pub fn neighbours_wrapping<'a>(
+        &'a self,
+        x: i64,
+        y: i64,
+        neighbourhood: Neighbourhood<'a>,
+    ) -> impl Iterator<Item = (Vec2<i64>, T)> + 'a {
+        neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(move |n| self.at_wrapping(n.x + x, n.y + y).map(|v| (*n, v)))
     }
 
     pub fn update(&mut self, x: i64, y: i64, v: T) {
@@ -86,6 +230,82 @@ impl<T: Clone> Grid<T> {
     }
 }
 
+impl<T: Clone> Grid<T> {
+    fn get(&self, x: usize, y: usize) -> T {
+        self.cells[x + y * self.width].clone()
+    }
+
+    /// Mirrors along the diagonal from the top left, swapping rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self.get(y, x))
+    }
+
+    /// Rotates by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.get(y, self.height - 1 - x)
+        })
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.get(self.width - 1 - x, self.height - 1 - y)
+        })
+    }
+
+    /// Rotates by 90 degrees counterclockwise, or 270 degrees clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| {
+            self.get(self.width - 1 - y, x)
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.get(self.width - 1 - x, y)
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |x, y| {
+            self.get(x, self.height - 1 - y)
+        })
+    }
+
+    /// The 8 rotations and reflections of the grid, without duplicates for
+    /// symmetric grids. The grid itself comes first.
+    pub fn orientations(&self) -> Vec<Self>
+    where
+        T: PartialEq,
+    {
+        let mut orientations: Vec<Self> = Vec::with_capacity(8);
+        let mut grid = self.clone();
+        for _ in 0..4 {
+            for oriented in [grid.clone(), grid.flip_horizontal()] {
+                if !orientations.contains(&oriented) {
+                    orientations.push(oriented);
+                }
+            }
+            grid = grid.rotate_cw();
+        }
+        orientations
+    }
+
+    /// A view of the `width` by `height` cells from `(x, y)`, `None` if they
+    /// are not all inside the grid.
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> Option<GridView<'_, T>> {
+        (x + width <= self.width && y + height <= self.height).then_some(GridView {
+            grid: self,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
 impl<T> Grid<T> {
     pub fn iter(&self) -> impl Iterator<Item = (Vec2<i64>, &T)> {
         self.cells.iter().enumerate().map(|(i, t)| {
@@ -96,3 +316,501 @@ impl<T> Grid<T> {
         })
     }
 }
+
+impl<T: GridChar> Grid<T> {
+    /// Drawing options, for rulers and highlights. A grid without any is
+    /// drawn by its `Display`.
+    pub fn render(&self) -> Render<'_, T> {
+        Render {
+            grid: self,
+            rulers: false,
+            highlights: HashSet::new(),
+            marker: None,
+        }
+    }
+}
+
+impl<T: GridChar> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.render().fmt(f)
+    }
+}
+
+impl<T: GridChar> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    /// Parses the rows of the grid, which must all be the same width.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in s.lines() {
+            let row_start = cells.len();
+            for (i, c) in line.char_indices() {
+                let cell = T::from_char(c).ok_or_else(|| {
+                    ParseError::new(s, &line[i..i + c.len_utf8()], format!("Unknown cell `{c}`"))
+                })?;
+                cells.push(cell);
+            }
+
+            let row_width = cells.len() - row_start;
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    let message = format!("Expected a row of width {w}, found {row_width}");
+                    return Err(ParseError::new(s, line, message));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+}
+
+/// A grid drawn with optional rulers and highlighted cells.
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    rulers: bool,
+    highlights: HashSet<Vec2<i64>>,
+    marker: Option<char>,
+}
+
+impl<T> Render<'_, T> {
+    /// Numbers the columns above the grid, one line per digit, and the rows
+    /// to its left.
+    pub fn rulers(mut self) -> Self {
+        self.rulers = true;
+        self
+    }
+
+    /// Draws the cells at `positions` in reverse video.
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = Vec2<i64>>) -> Self {
+        self.highlights.extend(positions);
+        self
+    }
+
+    /// Draws highlighted cells as `marker` instead, for output without colours.
+    pub fn marker(mut self, marker: char) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+}
+
+impl<T: GridChar> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let grid = self.grid;
+        let digits = |n: usize| n.max(1).to_string().len();
+        let column_digits = digits(grid.width.saturating_sub(1));
+        let row_digits = digits(grid.height.saturating_sub(1));
+
+        let mut lines = Vec::new();
+        if self.rulers {
+            for digit in (0..column_digits).rev() {
+                let ruler: String = (0..grid.width)
+                    .map(|x| {
+                        let place = 10usize.pow(digit as u32);
+                        if digit > 0 && x < place {
+                            ' '
+                        } else {
+                            char::from(b'0' + (x / place % 10) as u8)
+                        }
+                    })
+                    .collect();
+                lines.push(format!("{:row_digits$} {ruler}", ""));
+            }
+        }
+
+        for y in 0..grid.height {
+            let mut line = if self.rulers {
+                format!("{y:>row_digits$} ")
+            } else {
+                String::new()
+            };
+            for x in 0..grid.width {
+                let c = grid.cells[x + y * grid.width].to_char();
+                if !self.highlights.contains(&Vec2::new(x as i64, y as i64)) {
+                    line.push(c);
+                } else if let Some(marker) = self.marker {
+                    line.push(marker);
+                } else {
+                    line.push_str(&format!("\x1b[7m{c}\x1b[0m"));
+                }
+            }
+            lines.push(line);
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Netpbm image with a `scale` by `scale` square per cell, `channels`
+    /// bytes per pixel.
+    fn netpbm(
+        &self,
+        magic: &str,
+        scale: usize,
+        channels: usize,
+        pixel: impl Fn(&T) -> Vec<u8>,
+    ) -> Vec<u8> {
+        let width = self.width * scale;
+        let mut image = format!("{magic}\n{width} {}\n255\n", self.height * scale).into_bytes();
+        image.reserve(width * self.height * scale * channels);
+        for row in self.cells.chunks(self.width.max(1)) {
+            let pixels: Vec<Vec<u8>> = row.iter().map(&pixel).collect();
+            for _ in 0..scale {
+                for p in &pixels {
+                    for _ in 0..scale {
+                        image.extend(p);
+                    }
+                }
+            }
+        }
+        image
+    }
+
+    /// A binary PPM image, with the colour of every cell from `colour`.
+    pub fn to_ppm(&self, scale: usize, colour: impl Fn(&T) -> [u8; 3]) -> Vec<u8> {
+        self.netpbm("P6", scale, 3, |t| colour(t).to_vec())
+    }
+
+    /// A binary PGM image, with the brightness of every cell from `grey`.
+    pub fn to_pgm(&self, scale: usize, grey: impl Fn(&T) -> u8) -> Vec<u8> {
+        self.netpbm("P5", scale, 1, |t| vec![grey(t)])
+    }
+
+    pub fn write_ppm(
+        &self,
+        path: &Path,
+        scale: usize,
+        colour: impl Fn(&T) -> [u8; 3],
+    ) -> io::Result<()> {
+        fs::write(path, self.to_ppm(scale, colour))
+    }
+
+    pub fn write_pgm(&self, path: &Path, scale: usize, grey: impl Fn(&T) -> u8) -> io::Result<()> {
+        fs::write(path, self.to_pgm(scale, grey))
+    }
+}
+
+/// Numbered PPM images of a grid over the steps of a simulation, e.g.
+/// `frames/frame_00000.ppm`, to step through in an image viewer.
+pub struct Frames {
+    dir: PathBuf,
+    scale: usize,
+    next: usize,
+}
+
+impl Frames {
+    /// Creates `dir` if needed and removes the frames left over in it, so
+    /// that a shorter run does not end in stale frames. Other files are kept.
+    pub fn new(dir: impl Into<PathBuf>, scale: usize) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with("frame_") && name.ends_with(".ppm") && entry.file_type()?.is_file()
+            {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(Self {
+            dir,
+            scale,
+            next: 0,
+        })
+    }
+
+    /// Writes the next frame and returns its path.
+    pub fn add<T>(
+        &mut self,
+        grid: &Grid<T>,
+        colour: impl Fn(&T) -> [u8; 3],
+    ) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!("frame_{:05}.ppm", self.next));
+        grid.write_ppm(&path, self.scale, colour)?;
+        self.next += 1;
+        Ok(path)
+    }
+
+    /// Number of frames written so far.
+    pub fn len(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next == 0
+    }
+}
+
+/// A borrowed rectangle of a [`Grid`], with positions relative to its top left.
+#[derive(Clone, Copy)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a, T: Clone> GridView<'a, T> {
+    pub fn at(&self, x: i64, y: i64) -> Option<T> {
+        if x < 0 || x >= self.width as i64 || y < 0 || y >= self.height as i64 {
+            None
+        } else {
+            self.grid.at(x + self.x as i64, y + self.y as i64)
+        }
+    }
+
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| {
+            self.grid.get(x + self.x, y + self.y)
+        })
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2<i64>, &'a T)> + 'a {
+        let (x0, y0, width, grid) = (self.x, self.y, self.width, self.grid);
+        (0..self.height).flat_map(move |y| {
+            (0..width).map(move |x| {
+                (
+                    Vec2::new(x as i64, y as i64),
+                    &grid.cells[x + x0 + (y + y0) * grid.width],
+                )
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "abc
+def
+ghi";
+
+    fn values(neighbours: impl Iterator<Item = (Vec2<i64>, char)>) -> String {
+        let mut values: Vec<char> = neighbours.map(|(_, v)| v).collect();
+        values.sort();
+        values.into_iter().collect()
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::from_str(GRID, |c| c);
+        assert_eq!("abcdfghi", values(grid.neighbours(1, 1)));
+        assert_eq!("bde", values(grid.neighbours(0, 0)));
+        assert_eq!(
+            "bd",
+            values(grid.neighbours_in(0, 0, Neighbourhood::VonNeumann))
+        );
+        assert_eq!(
+            "e",
+            values(grid.neighbours_in(0, 0, Neighbourhood::Diagonal))
+        );
+        assert_eq!(
+            "fh",
+            values(grid.neighbours_in(0, 0, Neighbourhood::Custom(&KNIGHT_OFFSETS)))
+        );
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = Grid::from_str(GRID, |c| c);
+        assert_eq!(Some('i'), grid.at_wrapping(-1, -1));
+        assert_eq!(Some('b'), grid.at_wrapping(4, 3));
+        assert_eq!(
+            "bcdg",
+            values(grid.neighbours_wrapping(0, 0, Neighbourhood::VonNeumann))
+        );
+        assert_eq!(
+            "bcdefghi",
+            values(grid.neighbours_wrapping(0, 0, Neighbourhood::Moore))
+        );
+
+        let empty = Grid::new(0, 0, '.');
+        assert_eq!(None, empty.at_wrapping(1, 1));
+        assert_eq!(
+            0,
+            empty
+                .neighbours_wrapping(0, 0, Neighbourhood::Moore)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_within_radius() {
+        assert_eq!(VON_NEUMANN_OFFSETS.len(), within_radius(1, false).len());
+        assert_eq!(MOORE_OFFSETS.len(), within_radius(1, true).len());
+        assert_eq!(12, within_radius(2, false).len());
+        assert_eq!(24, within_radius(2, true).len());
+
+        let grid = Grid::from_str(GRID, |c| c);
+        let offsets = within_radius(2, false);
+        assert_eq!(
+            "bcdeg",
+            values(grid.neighbours_in(0, 0, Neighbourhood::Custom(&offsets)))
+        );
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = Grid::from_str("ab\ncd\nef", |c| c);
+        let rows = |grid: Grid<char>| -> Vec<String> {
+            grid.cells
+                .chunks(grid.width)
+                .map(|row| row.iter().collect())
+                .collect()
+        };
+        assert_eq!(vec!["ace", "bdf"], rows(grid.transpose()));
+        assert_eq!(vec!["eca", "fdb"], rows(grid.rotate_cw()));
+        assert_eq!(vec!["fe", "dc", "ba"], rows(grid.rotate_180()));
+        assert_eq!(vec!["bdf", "ace"], rows(grid.rotate_ccw()));
+        assert_eq!(vec!["ba", "dc", "fe"], rows(grid.flip_horizontal()));
+        assert_eq!(vec!["ef", "cd", "ab"], rows(grid.flip_vertical()));
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        assert_eq!(grid.rotate_180(), grid.rotate_cw().rotate_cw());
+    }
+
+    #[test]
+    fn test_orientations() {
+        let orientations = |s: &str| Grid::from_str(s, |c| c).orientations();
+        assert_eq!(8, orientations("ab\ncd").len());
+        assert_eq!(1, orientations("aa\naa").len());
+        assert_eq!(2, orientations("aaa\n.a.\naaa").len());
+        assert_eq!(4, orientations("aa.\n.aa").len());
+
+        let grid = Grid::from_str("ab\ncd", |c| c);
+        assert_eq!(grid, orientations("ab\ncd")[0]);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = Grid::from_str(GRID, |c| c);
+        let view = grid.view(1, 1, 2, 2).unwrap();
+        assert_eq!(Some('e'), view.at(0, 0));
+        assert_eq!(Some('i'), view.at(1, 1));
+        assert_eq!(None, view.at(2, 0));
+        assert_eq!(None, view.at(-1, 0));
+        assert_eq!(
+            vec![(Vec2::new(1, 0), &'f'), (Vec2::new(0, 1), &'h')],
+            view.iter()
+                .filter(|(p, _)| p.x + p.y == 1)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Grid::from_str("ef\nhi", |c| c), view.to_grid());
+
+        assert!(grid.view(2, 0, 2, 1).is_none());
+        assert_eq!(0, grid.view(3, 3, 0, 0).unwrap().iter().count());
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum Cell {
+        Empty,
+        Wall,
+    }
+
+    impl GridChar for Cell {
+        fn to_char(&self) -> char {
+            match self {
+                Cell::Empty => '.',
+                Cell::Wall => '#',
+            }
+        }
+
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '.' => Some(Cell::Empty),
+                '#' => Some(Cell::Wall),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = "#..#\n.##.\n....";
+        let grid: Grid<Cell> = input.parse().unwrap();
+        assert_eq!((4, 3), (grid.width, grid.height));
+        assert_eq!(Some(Cell::Wall), grid.at(1, 1));
+        assert_eq!(input, grid.to_string());
+        assert_eq!(grid, grid.to_string().parse().unwrap());
+        assert_eq!(grid, Grid::from_str(input, |c| Cell::from_char(c).unwrap()));
+
+        let error = "#..#\n.#x.".parse::<Grid<Cell>>().unwrap_err();
+        assert_eq!((2, 3), (error.line, error.column));
+        let error = "#..#\n.#.".parse::<Grid<Cell>>().unwrap_err();
+        assert_eq!("Expected a row of width 4, found 3", error.message);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: Grid<char> = "a".repeat(12).parse().unwrap();
+        assert_eq!(
+            "            11\n  012345678901\n0 aaaaaaaaaaaa",
+            grid.render().rulers().to_string()
+        );
+
+        let grid: Grid<char> = GRID.parse().unwrap();
+        let highlighted = grid.render().highlight([Vec2::new(1, 0), Vec2::new(2, 2)]);
+        assert_eq!("a*c\ndef\ngh*", highlighted.marker('*').to_string());
+        assert!(
+            grid.render()
+                .highlight([Vec2::new(0, 1)])
+                .to_string()
+                .contains("\x1b[7md\x1b[0m")
+        );
+    }
+
+    #[test]
+    fn test_images() {
+        let grid: Grid<Cell> = "#.\n..".parse().unwrap();
+        let colour = |c: &Cell| match c {
+            Cell::Wall => [255, 0, 0],
+            Cell::Empty => [0, 0, 0],
+        };
+
+        let ppm = grid.to_ppm(1, colour);
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(header, &ppm[..header.len()]);
+        assert_eq!(&[255, 0, 0, 0, 0, 0], &ppm[header.len()..header.len() + 6]);
+        assert_eq!(header.len() + 2 * 2 * 3, ppm.len());
+
+        let pgm = grid.to_pgm(2, |c| if *c == Cell::Wall { 200 } else { 0 });
+        let header = b"P5\n4 4\n255\n";
+        assert_eq!(header, &pgm[..header.len()]);
+        assert_eq!(
+            &[200, 200, 0, 0, 200, 200, 0, 0, 0, 0, 0, 0],
+            &pgm[header.len()..header.len() + 12]
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc25-frames-{}", std::process::id()));
+        let mut grid: Grid<Cell> = "..\n..".parse().unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("frame_00007.ppm"), "stale").unwrap();
+        fs::write(dir.join("notes.txt"), "kept").unwrap();
+        let mut frames = Frames::new(&dir, 1).unwrap();
+        assert!(!dir.join("frame_00007.ppm").exists());
+        assert!(dir.join("notes.txt").is_file());
+        for i in 0..3 {
+            grid.update(i % 2, i / 2, Cell::Wall);
+            frames.add(&grid, |_| [1, 2, 3]).unwrap();
+        }
+        assert_eq!(3, frames.len());
+        assert!(dir.join("frame_00002.ppm").is_file());
+        assert!(!dir.join("frame_00003.ppm").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,6 +1,10 @@
 pub mod equation_system;
 pub mod grid;
+pub mod panic;
+pub mod pathfinding;
+pub mod property;
 pub mod search;
+pub mod simd;
 pub mod spatial_grid;
 pub mod str_util;
 pub mod vec2;
@@ -0,0 +1,58 @@
+//! Catching panics of solutions, so that one failing day or implementation
+//! is reported instead of aborting the rest.
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, turning a panic into an error like `panicked: <message>`. The
+/// panic is not printed by the default hook, only panics outside of `catch`
+/// are.
+pub fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                previous(info);
+            }
+        }));
+    });
+
+    let quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(quiet);
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        format!("panicked: {message}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(Ok(3), catch(|| 1 + 2));
+        assert_eq!(Err("panicked: oops".to_string()), catch(|| panic!("oops")));
+        let value = 7;
+        assert_eq!(
+            Err("panicked: bad value 7".to_string()),
+            catch(|| panic!("bad value {value}"))
+        );
+        assert_eq!(
+            Err("panicked: unknown panic".to_string()),
+            catch(|| std::panic::panic_any(5))
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Shortest paths between the cells of a [`Grid`].
+//!
+//! Every search can start from several cells at once and returns the distance
+//! to every cell it reached, a predecessor for each to reconstruct a path, and
+//! the number of different shortest paths there.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::util::grid::{Grid, Neighbourhood};
+use crate::util::vec2::Vec2;
+
+pub struct Paths {
+    pub distances: Grid<Option<u64>>,
+    /// The cell each cell was first reached from on a shortest path, `None`
+    /// for the starts and unreached cells
+    pub predecessors: Grid<Option<Vec2<i64>>>,
+    /// Number of shortest paths from any of the starts, saturating at `u64::MAX`
+    pub counts: Grid<u64>,
+}
+
+impl Paths {
+    pub fn distance(&self, to: Vec2<i64>) -> Option<u64> {
+        self.distances.at(to.x, to.y).flatten()
+    }
+
+    pub fn count(&self, to: Vec2<i64>) -> u64 {
+        self.counts.at(to.x, to.y).unwrap_or(0)
+    }
+
+    /// A shortest path from one of the starts to `to`, both included.
+    pub fn path(&self, to: Vec2<i64>) -> Option<Vec<Vec2<i64>>> {
+        self.distance(to)?;
+        let mut path = vec![to];
+        while let Some(previous) = self
+            .predecessors
+            .at(path[path.len() - 1].x, path[path.len() - 1].y)
+            .flatten()
+        {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A* from `starts`, stopping once no shorter path to `target` is possible.
+/// Without a target or heuristic it is Dijkstra's algorithm.
+fn search<T: Clone>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Vec2<i64>>,
+    target: Option<Vec2<i64>>,
+    neighbourhood: Neighbourhood,
+    cost: impl Fn(&T) -> Option<u64>,
+    heuristic: impl Fn(Vec2<i64>) -> u64,
+) -> Paths {
+    let index = |p: Vec2<i64>| (p.x + p.y * grid.width as i64) as usize;
+    let position = |i: usize| Vec2::new((i % grid.width) as i64, (i / grid.width) as i64);
+
+    let mut paths = Paths {
+        distances: Grid::new(grid.width, grid.height, None),
+        predecessors: Grid::new(grid.width, grid.height, None),
+        counts: Grid::new(grid.width, grid.height, 0),
+    };
+    let mut done = vec![false; grid.cells.len()];
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        let i = index(start);
+        if grid.at(start.x, start.y).is_some() && paths.distances.cells[i].is_none() {
+            paths.distances.cells[i] = Some(0);
+            paths.counts.cells[i] = 1;
+            heap.push(Reverse((heuristic(start), 0, i)));
+        }
+    }
+
+    // Cells as far as the target are still expanded, so that the count of
+    // paths to it is complete
+    let mut target_estimate = None;
+    while let Some(Reverse((estimate, distance, i))) = heap.pop() {
+        if target_estimate.is_some_and(|t| estimate > t) {
+            break;
+        }
+        if done[i] {
+            continue;
+        }
+        done[i] = true;
+
+        let p = position(i);
+        if Some(p) == target {
+            target_estimate = Some(estimate);
+        }
+        for (offset, cell) in grid.neighbours_in(p.x, p.y, neighbourhood) {
+            let Some(step) = cost(&cell) else {
+                continue;
+            };
+            let next = p + offset;
+            let j = index(next);
+            let next_distance = distance + step;
+            match paths.distances.cells[j] {
+                Some(d) if next_distance > d => {}
+                Some(d) if next_distance == d => {
+                    paths.counts.cells[j] =
+                        paths.counts.cells[j].saturating_add(paths.counts.cells[i]);
+                }
+                _ => {
+                    paths.distances.cells[j] = Some(next_distance);
+                    paths.predecessors.cells[j] = Some(p);
+                    paths.counts.cells[j] = paths.counts.cells[i];
+                    heap.push(Reverse((next_distance + heuristic(next), next_distance, j)));
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Breadth-first search through the cells that are `passable`, one step each.
+pub fn bfs<T: Clone>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Vec2<i64>>,
+    neighbourhood: Neighbourhood,
+    passable: impl Fn(&T) -> bool,
+) -> Paths {
+    search(
+        grid,
+        starts,
+        None,
+        neighbourhood,
+        |t| passable(t).then_some(1),
+        |_| 0,
+    )
+}
+
+/// Dijkstra's algorithm, `cost` is the cost of stepping onto a cell or `None`
+/// if it cannot be entered. Costs must be positive for the counts to be right.
+pub fn dijkstra<T: Clone>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Vec2<i64>>,
+    neighbourhood: Neighbourhood,
+    cost: impl Fn(&T) -> Option<u64>,
+) -> Paths {
+    search(grid, starts, None, neighbourhood, cost, |_| 0)
+}
+
+/// A* towards `target`, with a `heuristic` that never overestimates the
+/// remaining cost and is consistent, e.g. the Manhattan distance for
+/// orthogonal steps of at least 1. Only the cells needed for the paths to
+/// `target` are guaranteed to have their final distance.
+pub fn a_star<T: Clone>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Vec2<i64>>,
+    target: Vec2<i64>,
+    neighbourhood: Neighbourhood,
+    cost: impl Fn(&T) -> Option<u64>,
+    heuristic: impl Fn(Vec2<i64>) -> u64,
+) -> Paths {
+    search(grid, starts, Some(target), neighbourhood, cost, heuristic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn find(grid: &Grid<char>, c: char) -> Vec2<i64> {
+        grid.iter().find(|(_, v)| **v == c).unwrap().0
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+        let paths = bfs(&grid, [start], Neighbourhood::VonNeumann, |&c| c != '#');
+
+        assert_eq!(Some(15), paths.distance(end));
+        assert_eq!(None, paths.distance(Vec2::new(3, 0)));
+        assert_eq!(0, paths.count(Vec2::new(3, 0)));
+
+        let path = paths.path(end).unwrap();
+        assert_eq!(16, path.len());
+        assert_eq!((start, end), (path[0], path[15]));
+        assert!(
+            path.windows(2)
+                .all(|w| (w[1] - w[0]).manhattan_distance() == 1)
+        );
+        assert!(path.iter().all(|p| grid.at(p.x, p.y) != Some('#')));
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let starts = [find(&grid, 'S'), find(&grid, 'E')];
+        let paths = bfs(&grid, starts, Neighbourhood::VonNeumann, |&c| c != '#');
+        assert_eq!(Some(0), paths.distance(starts[1]));
+        assert_eq!(Some(1), paths.distance(Vec2::new(7, 3)));
+        assert_eq!(Some(7), paths.distance(Vec2::new(4, 0)));
+        assert_eq!(
+            Some(vec![starts[1], Vec2::new(7, 3)]),
+            paths.path(Vec2::new(7, 3))
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let grid = Grid::new(3, 3, '.');
+        let paths = bfs(&grid, [Vec2::new(0, 0)], Neighbourhood::VonNeumann, |_| {
+            true
+        });
+        // Choose which 2 of the 4 steps go right
+        assert_eq!(6, paths.count(Vec2::new(2, 2)));
+        assert_eq!(1, paths.count(Vec2::new(0, 2)));
+
+        let paths = bfs(&grid, [Vec2::new(0, 0)], Neighbourhood::Moore, |_| true);
+        assert_eq!(Some(2), paths.distance(Vec2::new(2, 2)));
+        assert_eq!(1, paths.count(Vec2::new(2, 2)));
+        assert_eq!(2, paths.count(Vec2::new(2, 1)));
+    }
+
+    #[test]
+    fn test_dijkstra_and_a_star() {
+        let grid = Grid::from_str("19111\n11191\n99991\n11111", |c| {
+            c.to_digit(10).unwrap() as u64
+        });
+        let (start, end) = (Vec2::new(0, 0), Vec2::new(0, 3));
+
+        let paths = dijkstra(&grid, [start], Neighbourhood::VonNeumann, |&c| Some(c));
+        assert_eq!(Some(11), paths.distance(end));
+        assert_eq!(
+            11,
+            paths.path(end).unwrap()[1..]
+                .iter()
+                .map(|p| grid.at(p.x, p.y).unwrap())
+                .sum::<u64>()
+        );
+
+        let manhattan = |p: Vec2<i64>| (p - end).manhattan_distance() as u64;
+        let found = a_star(
+            &grid,
+            [start],
+            end,
+            Neighbourhood::VonNeumann,
+            |&c| Some(c),
+            manhattan,
+        );
+        assert_eq!(paths.distance(end), found.distance(end));
+        assert_eq!(paths.path(end), found.path(end));
+        assert_eq!(paths.count(end), found.count(end));
+
+        let walled = a_star(
+            &grid,
+            [start],
+            end,
+            Neighbourhood::VonNeumann,
+            |&c| (c < 9).then_some(c),
+            manhattan,
+        );
+        // Around the right, through the only gap in the row of 9s
+        assert_eq!(Some(13), walled.distance(end));
+        assert_eq!(14, walled.path(end).unwrap().len());
+        let blocked = a_star(
+            &grid,
+            [start],
+            end,
+            Neighbourhood::VonNeumann,
+            |&c| (c > 1).then_some(c),
+            manhattan,
+        );
+        assert_eq!(None, blocked.distance(end));
+        assert_eq!(None, blocked.path(end));
+    }
+}
@@ -0,0 +1,160 @@
+//! Differential property testing: run an optimized solver and a naive
+//! reference on many random inputs, and shrink the first input they disagree
+//! on to a minimal one.
+
+use std::fmt::Debug;
+
+use crate::generate::Rng;
+use crate::util::panic::catch;
+
+/// Upper bound on shrinking steps, in case shrinking does not converge
+const MAX_SHRINKS: usize = 1000;
+
+/// A generated test case that can be made smaller.
+pub trait Case: Clone {
+    /// The case as puzzle input.
+    fn input(&self) -> String;
+
+    /// Smaller variants of the case, the most promising first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+/// Why `fast` and `reference` disagree on `case`, if they do.
+fn mismatch<T: Case, R: PartialEq + Debug>(
+    case: &T,
+    fast: &impl Fn(&str) -> R,
+    reference: &impl Fn(&str) -> R,
+) -> Option<String> {
+    let input = case.input();
+    let expected = match catch(|| reference(&input)) {
+        Ok(expected) => expected,
+        Err(e) => return Some(format!("reference {e}")),
+    };
+    match catch(|| fast(&input)) {
+        Ok(actual) if actual == expected => None,
+        Ok(actual) => Some(format!("expected {expected:?}, got {actual:?}")),
+        Err(e) => Some(format!("expected {expected:?}, {e}")),
+    }
+}
+
+/// Greedily replaces the case with the first smaller variant that still
+/// fails, until none does.
+fn shrink<T: Case>(
+    mut case: T,
+    mut reason: String,
+    fails: impl Fn(&T) -> Option<String>,
+) -> (T, String, usize) {
+    let mut steps = 0;
+    'shrink: while steps < MAX_SHRINKS {
+        for smaller in case.shrink() {
+            if let Some(smaller_reason) = fails(&smaller) {
+                case = smaller;
+                reason = smaller_reason;
+                steps += 1;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+    (case, reason, steps)
+}
+
+/// Checks that `fast` gives the same answer as `reference` on `cases` inputs
+/// from `generate`, seeded with `0..cases`. A panic in `fast` counts as a
+/// wrong answer.
+///
+/// Panics with the shrunk input on the first disagreement.
+pub fn differential<T: Case, R: PartialEq + Debug>(
+    cases: u64,
+    generate: impl Fn(&mut Rng) -> T,
+    fast: impl Fn(&str) -> R,
+    reference: impl Fn(&str) -> R,
+) {
+    for seed in 0..cases {
+        let case = generate(&mut Rng::new(seed));
+        let Some(reason) = mismatch(&case, &fast, &reference) else {
+            continue;
+        };
+
+        let (case, reason, steps) = shrink(case, reason, |c| mismatch(c, &fast, &reference));
+        panic!(
+            "Seed {seed} failed, shrunk in {steps} steps to:\n{}\n{reason}",
+            case.input()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Numbers(Vec<u64>);
+
+    impl Case for Numbers {
+        fn input(&self) -> String {
+            let numbers: Vec<_> = self.0.iter().map(u64::to_string).collect();
+            numbers.join("\n")
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.0.len() {
+                let mut removed = self.0.clone();
+                removed.remove(i);
+                smaller.push(Numbers(removed));
+            }
+            for i in 0..self.0.len() {
+                if self.0[i] > 0 {
+                    let mut halved = self.0.clone();
+                    halved[i] /= 2;
+                    smaller.push(Numbers(halved));
+                    let mut decremented = self.0.clone();
+                    decremented[i] -= 1;
+                    smaller.push(Numbers(decremented));
+                }
+            }
+            smaller
+        }
+    }
+
+    fn numbers(rng: &mut Rng) -> Numbers {
+        Numbers((0..rng.range(1, 20)).map(|_| rng.below(100)).collect())
+    }
+
+    fn sum(input: &str) -> u64 {
+        input.lines().map(|l| l.parse::<u64>().unwrap()).sum()
+    }
+
+    #[test]
+    fn test_agreeing() {
+        differential(50, numbers, sum, |input| {
+            input.lines().map(|l| l.parse::<u64>().unwrap()).sum()
+        });
+    }
+
+    #[test]
+    fn test_shrinks_to_minimal() {
+        // Wrong as soon as a number of at least 50 is in the input
+        let buggy = |input: &str| {
+            let sum = sum(input);
+            if input.lines().any(|l| l.parse::<u64>().unwrap() >= 50) {
+                sum + 1
+            } else {
+                sum
+            }
+        };
+        let message = catch(|| differential(50, numbers, buggy, sum)).unwrap_err();
+        assert!(message.ends_with(":\n50\nexpected 50, got 51"), "{message}");
+
+        let panicking = |input: &str| {
+            assert!(sum(input) <= 30, "too large");
+            sum(input)
+        };
+        let message = catch(|| differential(50, numbers, panicking, sum)).unwrap_err();
+        assert!(
+            message.ends_with(":\n31\nexpected 31, panicked: too large"),
+            "{message}"
+        );
+    }
+}
@@ -1,17 +1,19 @@
 /// Find the index of the first element in a sorted list that is larger than or
-/// equal to the find argument 
-/// 
-/// # Arguments 
-/// 
+/// equal to the find argument
+///
+/// # Arguments
+///
 /// * `list`: A sorted list
 /// * `find`: The element to find
-/// 
-/// returns: usize 
-/// 
-/// # Examples 
-/// 
+///
+/// returns: usize
+///
+/// # Examples
+///
 /// ```
-/// assert_eq!(binary_search_leftmost([1, 2, 3, 4], 2), 1);
+/// use aoc25::util::search::binary_search_leftmost;
+///
+/// assert_eq!(binary_search_leftmost(&[1, 2, 3, 4], 2), 1);
 /// ```
 pub fn binary_search_leftmost<T: PartialOrd>(list: &[T], find: T) -> usize {
     let mut min = 0;
@@ -119,4 +121,4 @@ mod tests {
         assert_eq!(binary_search_rightmost(&l, 12), 5);
         assert_eq!(binary_search_rightmost(&l, 13), 5);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,210 @@
+//! The subset of `std::simd` used by the solutions.
+//!
+//! With the `simd` feature (nightly only) this re-exports `std::simd`.
+//! Otherwise the same names refer to the plain array based types in [`scalar`],
+//! which work on stable Rust and give identical results. Import the traits
+//! from here as well so the kernels compile unchanged either way.
+
+#[cfg(feature = "simd")]
+pub use std::simd::cmp::{SimdOrd, SimdPartialOrd};
+#[cfg(feature = "simd")]
+pub use std::simd::num::SimdInt;
+#[cfg(feature = "simd")]
+pub use std::simd::{Mask, Simd, i64x8};
+
+#[cfg(not(feature = "simd"))]
+pub use scalar::{Mask, Simd, SimdInt, SimdOrd, SimdPartialOrd, i64x8};
+
+pub mod scalar {
+    use std::array;
+    use std::marker::PhantomData;
+    use std::ops::{Add, BitAnd, Mul, MulAssign, Sub};
+
+    #[allow(non_camel_case_types)]
+    pub type i64x8 = Simd<i64, 8>;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Simd<T, const N: usize>([T; N]);
+
+    impl<T: Copy, const N: usize> Simd<T, N> {
+        pub fn splat(value: T) -> Self {
+            Self([value; N])
+        }
+
+        /// Takes the first `N` elements of `slice`.
+        pub fn from_slice(slice: &[T]) -> Self {
+            Self(array::from_fn(|i| slice[i]))
+        }
+
+        pub fn to_array(self) -> [T; N] {
+            self.0
+        }
+
+        fn map(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+            Self(array::from_fn(|i| f(self.0[i], other.0[i])))
+        }
+    }
+
+    // Integer lanes wrap on overflow like `std::simd`
+    impl<const N: usize> Add for Simd<i64, N> {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self {
+            self.map(rhs, i64::wrapping_add)
+        }
+    }
+
+    impl<const N: usize> Sub for Simd<i64, N> {
+        type Output = Self;
+
+        fn sub(self, rhs: Self) -> Self {
+            self.map(rhs, i64::wrapping_sub)
+        }
+    }
+
+    impl<const N: usize> Mul for Simd<i64, N> {
+        type Output = Self;
+
+        fn mul(self, rhs: Self) -> Self {
+            self.map(rhs, i64::wrapping_mul)
+        }
+    }
+
+    impl<const N: usize> MulAssign for Simd<i64, N> {
+        fn mul_assign(&mut self, rhs: Self) {
+            *self = *self * rhs;
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub struct Mask<T, const N: usize>([bool; N], PhantomData<T>);
+
+    impl<T, const N: usize> Mask<T, N> {
+        pub fn splat(value: bool) -> Self {
+            Self([value; N], PhantomData)
+        }
+
+        pub fn any(self) -> bool {
+            self.0.iter().any(|&b| b)
+        }
+
+        pub fn to_array(self) -> [bool; N] {
+            self.0
+        }
+    }
+
+    impl<T, const N: usize> BitAnd for Mask<T, N> {
+        type Output = Self;
+
+        fn bitand(self, rhs: Self) -> Self {
+            Self(array::from_fn(|i| self.0[i] & rhs.0[i]), PhantomData)
+        }
+    }
+
+    pub trait SimdInt {
+        type Scalar;
+
+        fn abs(self) -> Self;
+        fn reduce_max(self) -> Self::Scalar;
+    }
+
+    impl<const N: usize> SimdInt for Simd<i64, N> {
+        type Scalar = i64;
+
+        fn abs(self) -> Self {
+            Self(self.0.map(i64::wrapping_abs))
+        }
+
+        fn reduce_max(self) -> i64 {
+            self.0.into_iter().max().unwrap_or(i64::MIN)
+        }
+    }
+
+    pub trait SimdOrd {
+        fn simd_max(self, other: Self) -> Self;
+    }
+
+    impl<T: Copy + Ord, const N: usize> SimdOrd for Simd<T, N> {
+        fn simd_max(self, other: Self) -> Self {
+            self.map(other, T::max)
+        }
+    }
+
+    pub trait SimdPartialOrd {
+        type Mask;
+
+        fn simd_ge(self, other: Self) -> Self::Mask;
+        fn simd_le(self, other: Self) -> Self::Mask;
+    }
+
+    impl<T: Copy + PartialOrd, const N: usize> SimdPartialOrd for Simd<T, N> {
+        type Mask = Mask<T, N>;
+
+        fn simd_ge(self, other: Self) -> Mask<T, N> {
+            Mask(array::from_fn(|i| self.0[i] >= other.0[i]), PhantomData)
+        }
+
+        fn simd_le(self, other: Self) -> Mask<T, N> {
+            Mask(array::from_fn(|i| self.0[i] <= other.0[i]), PhantomData)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scalar::{self, SimdInt as _, SimdOrd as _, SimdPartialOrd as _};
+
+    const A: [i64; 8] = [3, -7, 0, 12, -1000, 5, -1, i64::MAX];
+    const B: [i64; 8] = [-2, 4, 0, 12, 9, -5, 6, 100];
+
+    #[test]
+    fn test_scalar() {
+        let a = scalar::i64x8::from_slice(&A);
+        let b = scalar::i64x8::from_slice(&B);
+
+        assert_eq!(
+            [1, -3, 0, 24, -991, 0, 5, i64::MIN + 99],
+            (a + b).to_array()
+        );
+        assert_eq!(
+            [5, 11, 0, 0, 1009, 10, 7, i64::MAX - 100],
+            (a - b).abs().to_array()
+        );
+        assert_eq!([3, 4, 0, 12, 9, 5, 6, i64::MAX], a.simd_max(b).to_array());
+        assert_eq!(i64::MAX, a.reduce_max());
+        assert_eq!(
+            [true, false, true, true, false, true, false, true],
+            a.simd_ge(b).to_array()
+        );
+        assert!(!(a.simd_ge(b) & a.simd_le(scalar::Simd::splat(-10))).any());
+    }
+
+    /// The scalar types must behave exactly like `std::simd`.
+    #[cfg(feature = "simd")]
+    #[test]
+    fn test_matches_std() {
+        use std::simd::cmp::{SimdOrd, SimdPartialOrd};
+        use std::simd::num::SimdInt;
+        use std::simd::{Mask, i64x8};
+
+        let (a, b) = (i64x8::from_array(A), i64x8::from_array(B));
+        let (sa, sb) = (scalar::i64x8::from_slice(&A), scalar::i64x8::from_slice(&B));
+
+        let mut product = a;
+        product *= b;
+        let mut scalar_product = sa;
+        scalar_product *= sb;
+
+        assert_eq!((a + b).to_array(), (sa + sb).to_array());
+        assert_eq!((a - b).abs().to_array(), (sa - sb).abs().to_array());
+        assert_eq!(product.to_array(), scalar_product.to_array());
+        assert_eq!(a.simd_max(b).to_array(), sa.simd_max(sb).to_array());
+        assert_eq!(a.reduce_max(), sa.reduce_max());
+
+        let mask = Mask::splat(true) & a.simd_ge(b) & a.simd_le(i64x8::splat(5));
+        let scalar_mask =
+            scalar::Mask::splat(true) & sa.simd_ge(sb) & sa.simd_le(scalar::Simd::splat(5));
+        assert_eq!(mask.to_array(), scalar_mask.to_array());
+        assert_eq!(mask.any(), scalar_mask.any());
+    }
+}
@@ -1,21 +1,20 @@
+use crate::util::simd::{Mask, Simd, SimdPartialOrd, i64x8};
 use std::array;
-use std::simd::cmp::SimdPartialOrd;
-use std::simd::{Mask, Simd, i64x8};
 
-struct GridCell<const Dimensions: usize> {
+struct GridCell<const DIMENSIONS: usize> {
     ids: Vec<usize>,
-    positions: [Vec<i64>; Dimensions],
+    positions: [Vec<i64>; DIMENSIONS],
 }
 
-impl<const Dimensions: usize> GridCell<Dimensions> {
-    pub fn add_point(&mut self, id: usize, pos: &[i64; Dimensions]) {
+impl<const DIMENSIONS: usize> GridCell<DIMENSIONS> {
+    pub fn add_point(&mut self, id: usize, pos: &[i64; DIMENSIONS]) {
         self.ids.push(id);
         for (d, vec) in self.positions.iter_mut().enumerate() {
             vec.push(pos[d]);
         }
     }
 
-    pub fn has_points_in_bbox(&self, min: &[i64; Dimensions], max: &[i64; Dimensions]) -> bool {
+    pub fn has_points_in_bbox(&self, min: &[i64; DIMENSIONS], max: &[i64; DIMENSIONS]) -> bool {
         let num_items = self.ids.len();
 
         let min_simds: Vec<i64x8> = min.iter().map(|&m| Simd::splat(m)).collect();
@@ -35,7 +34,7 @@ impl<const Dimensions: usize> GridCell<Dimensions> {
                 }
             } else {
                 let mut test_mask = Mask::splat(true);
-                for d in 0..Dimensions {
+                for d in 0..DIMENSIONS {
                     let dv = Simd::from_slice(&self.positions[d][i..i + 8]);
                     test_mask = test_mask & dv.simd_ge(min_simds[d]) & dv.simd_le(max_simds[d]);
                 }
@@ -49,19 +48,19 @@ impl<const Dimensions: usize> GridCell<Dimensions> {
     }
 }
 
-pub struct SpatialGrid<const Dimensions: usize, const Splits: usize> {
-    cells: Vec<GridCell<Dimensions>>,
-    min: [i64; Dimensions],
-    max: [i64; Dimensions],
+pub struct SpatialGrid<const DIMENSIONS: usize, const SPLITS: usize> {
+    cells: Vec<GridCell<DIMENSIONS>>,
+    min: [i64; DIMENSIONS],
+    max: [i64; DIMENSIONS],
 }
 
-impl<const Dimensions: usize, const Splits: usize> SpatialGrid<Dimensions, Splits> {
-    pub fn new(min: &[i64; Dimensions], max: &[i64; Dimensions]) -> Self {
-        let num_cells = Splits.pow(Dimensions as u32);
+impl<const DIMENSIONS: usize, const SPLITS: usize> SpatialGrid<DIMENSIONS, SPLITS> {
+    pub fn new(min: &[i64; DIMENSIONS], max: &[i64; DIMENSIONS]) -> Self {
+        let num_cells = SPLITS.pow(DIMENSIONS as u32);
         let mut cells = Vec::with_capacity(num_cells);
 
-        for i in 0..num_cells {
-            let positions: [Vec<i64>; Dimensions] = (0..Dimensions)
+        for _i in 0..num_cells {
+            let positions: [Vec<i64>; DIMENSIONS] = (0..DIMENSIONS)
                 .map(|_| Vec::<i64>::new())
                 .collect::<Vec<_>>()
                 .try_into()
@@ -75,19 +74,19 @@ impl<const Dimensions: usize, const Splits: usize> SpatialGrid<Dimensions, Split
 
         Self {
             cells,
-            min: min.clone(),
-            max: max.clone(),
+            min: *min,
+            max: *max,
         }
     }
 
-    pub fn add_point(&mut self, id: usize, pos: &[i64; Dimensions]) {
+    pub fn add_point(&mut self, id: usize, pos: &[i64; DIMENSIONS]) {
         let idx = self.get_cell_idx(pos).expect("Point is out of bounds");
         let cell = &mut self.cells[idx];
 
         cell.add_point(id, pos);
     }
 
-    fn get_dimensional_idx(&self, pos: &[i64; Dimensions]) -> Option<[usize; Dimensions]> {
+    fn get_dimensional_idx(&self, pos: &[i64; DIMENSIONS]) -> Option<[usize; DIMENSIONS]> {
         for (d, &p) in pos.iter().enumerate() {
             if self.min[d] > p || self.max[d] < p {
                 return None;
@@ -95,25 +94,25 @@ impl<const Dimensions: usize, const Splits: usize> SpatialGrid<Dimensions, Split
         }
 
         Some(array::from_fn(|i| {
-            ((Splits as i64 * (pos[i] - self.min[i])) / self.max[i]) as usize
+            ((SPLITS as i64 * (pos[i] - self.min[i])) / self.max[i]) as usize
         }))
     }
 
-    fn flatten_idx(&self, idx: &[usize; Dimensions]) -> usize {
-        idx.iter().cloned().reduce(|a, v| (a * Splits + v)).unwrap()
+    fn flatten_idx(&self, idx: &[usize; DIMENSIONS]) -> usize {
+        idx.iter().cloned().reduce(|a, v| a * SPLITS + v).unwrap()
     }
 
-    fn get_cell_idx(&self, pos: &[i64; Dimensions]) -> Option<usize> {
+    fn get_cell_idx(&self, pos: &[i64; DIMENSIONS]) -> Option<usize> {
         let dim_idx = self.get_dimensional_idx(pos)?;
 
         Some(self.flatten_idx(&dim_idx))
     }
 
-    fn clamp(&self, pos: &[i64; Dimensions]) -> [i64; Dimensions] {
+    fn clamp(&self, pos: &[i64; DIMENSIONS]) -> [i64; DIMENSIONS] {
         array::from_fn(|i| pos[i].clamp(self.min[i], self.max[i]))
     }
 
-    pub fn bbox_contains_point(&self, min: &[i64; Dimensions], max: &[i64; Dimensions]) -> bool {
+    pub fn bbox_contains_point(&self, min: &[i64; DIMENSIONS], max: &[i64; DIMENSIONS]) -> bool {
         let min = self.clamp(min);
         let max = self.clamp(max);
 
@@ -121,7 +120,7 @@ impl<const Dimensions: usize, const Splits: usize> SpatialGrid<Dimensions, Split
         let max_idx = self.get_dimensional_idx(&max).expect("Cell out of bounds");
         let mut current = min_idx;
 
-        while current[Dimensions - 1] <= max_idx[Dimensions - 1] {
+        while current[DIMENSIONS - 1] <= max_idx[DIMENSIONS - 1] {
             let flat_idx = self.flatten_idx(&current);
             let cell = &self.cells[flat_idx];
 
@@ -129,12 +128,12 @@ impl<const Dimensions: usize, const Splits: usize> SpatialGrid<Dimensions, Split
                 return true;
             }
 
-            for d in 0..Dimensions {
+            for d in 0..DIMENSIONS {
                 current[d] += 1;
-                if current[d] > max_idx[d] && d < (Dimensions - 1) {
+                if current[d] > max_idx[d] && d < (DIMENSIONS - 1) {
                     current[d] = min_idx[d];
                 } else {
-                    break
+                    break;
                 }
             }
         }
@@ -151,7 +150,7 @@ mod tests {
     fn test_non_simd() {
         let mut grid = SpatialGrid::<3, 2>::new(&[0, 0, 0], &[10, 10, 10]);
         grid.add_point(0, &[1, 1, 1]);
-        
+
         assert!(grid.bbox_contains_point(&[0, 0, 0], &[2, 2, 2]));
         assert!(!grid.bbox_contains_point(&[2, 2, 2], &[4, 4, 4]));
     }
@@ -159,7 +158,7 @@ mod tests {
     #[test]
     fn test_simd() {
         let mut grid = SpatialGrid::<3, 2>::new(&[0, 0, 0], &[10, 10, 10]);
-        
+
         for i in 0..32 {
             grid.add_point(i, &[1, 1, 1]);
         }
@@ -167,4 +166,4 @@ mod tests {
         assert!(grid.bbox_contains_point(&[0, 0, 0], &[2, 2, 2]));
         assert!(!grid.bbox_contains_point(&[2, 2, 2], &[4, 4, 4]));
     }
-}
\ No newline at end of file
+}
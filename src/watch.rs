@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc25::answers::{ANSWERS_DIR, AnswerFile};
+use aoc25::inputs::InputLocator;
+use aoc25::report::answers_from_csv;
+use aoc25::solution::PartAnswer;
+
+use crate::cli::RunArgs;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Modification times of `paths` and every file below the directories among
+/// them. Missing paths are left out, so creating one counts as a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    fn add(path: &Path, snapshot: &mut Snapshot) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                add(&entry.path(), snapshot);
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            snapshot.insert(path.to_path_buf(), modified);
+        }
+    }
+
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        add(path, &mut snapshot);
+    }
+    snapshot
+}
+
+/// Files that were added, removed or modified between two snapshots.
+fn changes(old: &Snapshot, new: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = new
+        .iter()
+        .filter(|&(path, time)| old.get(path) != Some(time))
+        .map(|(path, _)| path.clone())
+        .chain(old.keys().filter(|p| !new.contains_key(*p)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// Arguments for running the day the same way in a child process.
+fn forwarded_args(args: &RunArgs) -> Vec<String> {
+    let mut forwarded = vec![args.days[0].to_string()];
+    for part in &args.parts {
+        forwarded.extend(["--part".to_string(), part.to_string()]);
+    }
+    if let Some(input) = &args.input {
+        forwarded.extend(["--input".to_string(), input.display().to_string()]);
+    }
+    if let Some(dir) = &args.input_dir {
+        forwarded.extend(["--input-dir".to_string(), dir.display().to_string()]);
+    }
+    if let Some(name) = &args.input_name {
+        forwarded.extend(["--name".to_string(), name.clone()]);
+    }
+    if args.profile {
+        forwarded.push("--profile".to_string());
+    }
+    forwarded
+}
+
+fn print_diff(previous: &[PartAnswer], answers: &[PartAnswer]) {
+    let mut unchanged = true;
+    for answer in answers {
+        let Some(old) = previous.iter().find(|a| a.part == answer.part) else {
+            continue;
+        };
+        if old.answer != answer.answer {
+            unchanged = false;
+            println!(
+                "Part {} changed: {} -> {}",
+                answer.part, old.answer, answer.answer
+            );
+        }
+    }
+    if unchanged {
+        println!("Answers unchanged");
+    }
+}
+
+fn cargo(args: &[&str]) -> Command {
+    let mut command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    command.args(args);
+    command
+}
+
+/// Runs the example tests of the day and, if they pass, the day itself.
+/// Returns the answers of the run, if it succeeded.
+fn run_once(args: &RunArgs, report: &Path) -> Option<Vec<PartAnswer>> {
+    let day = args.days[0];
+
+    println!("Testing day {day}");
+    let filter = format!("day{day}::");
+    let tested = cargo(&["test", "-q", "--lib", "--", &filter]).status();
+    if !tested.is_ok_and(|s| s.success()) {
+        println!("Tests failed, not running the input");
+        return None;
+    }
+
+    let report_arg = report.display().to_string();
+    let mut run_args = vec!["run", "-q", "--release", "--", "--report-file", &report_arg];
+    let forwarded = forwarded_args(args);
+    run_args.extend(forwarded.iter().map(String::as_str));
+
+    let _ = fs::remove_file(report);
+    let ran = cargo(&run_args).status();
+    if !ran.is_ok_and(|s| s.success()) {
+        println!("Day {day} failed");
+        return None;
+    }
+
+    let csv = fs::read_to_string(report).ok()?;
+    let _ = fs::remove_file(report);
+    match answers_from_csv(&csv) {
+        Ok(answers) => Some(answers.into_iter().map(|(_, a)| a).collect()),
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    }
+}
+
+/// Polls the sources and inputs of the day, rebuilding and re-running it
+/// whenever one of them changes. Runs until interrupted.
+pub fn watch(args: &RunArgs) -> Result<(), String> {
+    let day = args.days[0];
+    let mut watched = vec![
+        PathBuf::from("src"),
+        PathBuf::from("Cargo.toml"),
+        AnswerFile::path(Path::new(ANSWERS_DIR), day),
+    ];
+    match &args.input {
+        Some(input) => watched.push(input.clone()),
+        None => watched.extend(
+            InputLocator::from_env(args.input_dir.as_deref(), args.input_name.clone())
+                .candidates(day),
+        ),
+    }
+    if !Path::new("src").is_dir() {
+        return Err("No src directory, run this from the repository root".to_string());
+    }
+
+    let report = env::temp_dir().join(format!("aoc25-watch-{}.csv", process::id()));
+    let mut previous: Option<Vec<PartAnswer>> = None;
+    let mut last = snapshot(&watched);
+    let mut changed: Vec<PathBuf> = Vec::new();
+
+    loop {
+        if !changed.is_empty() {
+            let names: Vec<_> = changed.iter().map(|p| p.display().to_string()).collect();
+            println!("\nChanged: {}", names.join(", "));
+        }
+
+        let answers = run_once(args, &report);
+        if let (Some(previous), Some(answers)) = (&previous, &answers) {
+            print_diff(previous, answers);
+        }
+        previous = answers.or(previous);
+        println!("Watching for changes, press Ctrl-C to stop");
+
+        // Compare with the snapshot from before the run, so edits made while
+        // building are picked up as well
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(&watched);
+            changed = changes(&last, &current);
+            last = current;
+            if !changed.is_empty() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc25::solution::Part;
+
+    #[test]
+    fn test_changes() {
+        let dir = env::temp_dir().join(format!("aoc25-watch-test-{}", process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        let file = dir.join("sub").join("day10.rs");
+        fs::write(&file, "a").unwrap();
+
+        let before = snapshot(std::slice::from_ref(&dir));
+        assert_eq!(1, before.len());
+        assert!(changes(&before, &snapshot(std::slice::from_ref(&dir))).is_empty());
+
+        let input = dir.join("day10.txt");
+        fs::write(&input, "b").unwrap();
+        fs::remove_file(&file).unwrap();
+        let after = snapshot(&[dir.clone(), dir.join("missing.txt")]);
+        assert_eq!(vec![input, file], changes(&before, &after));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_forwarded_args() {
+        let args = RunArgs {
+            days: vec![10],
+            parts: vec![Part::Two],
+            input: None,
+            input_dir: Some(PathBuf::from("puzzles")),
+            input_name: Some("alt".to_string()),
+            profile: true,
+        };
+        assert_eq!(
+            "10 --part 2 --input-dir puzzles --name alt --profile",
+            forwarded_args(&args).join(" ")
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! The counting allocator is installed for this test binary only, the unit
+//! tests of the library keep the system allocator.
+
+use std::hint::black_box;
+
+use aoc25::alloc::{AllocStats, CountingAllocator, enabled, measure};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    assert!(enabled());
+
+    let (_, stats) = measure(|| {
+        let kept = black_box(vec![0u8; 1000]);
+        let (_, inner) = measure(|| drop(black_box(vec![0u8; 5000])));
+        assert_eq!(5000, inner.peak);
+        kept
+    });
+
+    assert_eq!(2, stats.allocations);
+    assert_eq!(6000, stats.bytes);
+    assert_eq!(6000, stats.peak);
+
+    let (_, stats) = measure(|| black_box(1 + 1));
+    assert_eq!(AllocStats::default(), stats);
+}